@@ -241,13 +241,14 @@ async function autoProcessPayment(requirements, popup) {
     const signedTxHex = await walletService.signTransaction({
       to: requirements.recipient,
       amount: requirements.amount,
+      nonce: requirements.nonce,
     });
 
     updatePaymentStatus('Transaction signed');
     updatePaymentStatus('Submitting to server...');
     logger.info('Submitting payment');
 
-    const response = await apiService.paid(signedTxHex);
+    const response = await apiService.paid(signedTxHex, requirements.nonce);
 
     if (response.ok) {
       updatePaymentStatus('Verifying on blockchain...');
@@ -257,7 +258,7 @@ async function autoProcessPayment(requirements, popup) {
       updatePaymentStatus('Transaction confirmed', true);
 
       logger.success('Payment successful');
-      logger.info(`Tx: ${response.data.transaction_hash}`);
+      logger.info(`Tx: ${response.data.settlement.tx_hash}`);
       showResult('paid-result', response.data, 'success');
 
       setTimeout(async () => {
@@ -310,16 +311,17 @@ async function payAndRetry() {
     const signedTxHex = await walletService.signTransaction({
       to: currentPaymentRequirements.recipient,
       amount: currentPaymentRequirements.amount,
+      nonce: currentPaymentRequirements.nonce,
     });
 
     btn.textContent = 'Submitting...';
     logger.info('Submitting payment');
 
-    const response = await apiService.paid(signedTxHex);
+    const response = await apiService.paid(signedTxHex, currentPaymentRequirements.nonce);
 
     if (response.ok) {
       logger.success('Payment successful');
-      logger.info(`Tx: ${response.data.transaction_hash}`);
+      logger.info(`Tx: ${response.data.settlement.tx_hash}`);
       showResult('paid-result', response.data, 'success');
       document.getElementById('payment-requirements').classList.add('hidden');
       currentPaymentRequirements = null;
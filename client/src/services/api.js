@@ -45,13 +45,16 @@ class APIService {
     return this.request('/api/free');
   }
 
-  async paid(paymentHeader = null) {
+  async paid(paymentHeader = null, paymentNonce = null) {
     const options = {};
 
     if (paymentHeader) {
       options.headers = {
         'x-payment': paymentHeader,
       };
+      if (paymentNonce) {
+        options.headers['x-payment-nonce'] = paymentNonce;
+      }
     }
 
     return this.request('/api/paid', options);
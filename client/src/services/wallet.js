@@ -94,7 +94,11 @@ class WalletService {
     try {
       const nonce = await this.api.rpc.system.accountNextIndex(this.keypair.address);
       const transfer = this.api.tx.balances.transferKeepAlive(tx.to, tx.amount);
-      const signed = await transfer.signAsync(this.keypair, { nonce });
+      // Bind the payment to the server's 402 challenge when it issued a nonce
+      const call = tx.nonce
+        ? this.api.tx.utility.batchAll([transfer, this.api.tx.system.remarkWithEvent(tx.nonce)])
+        : transfer;
+      const signed = await call.signAsync(this.keypair, { nonce });
       const hex = signed.toHex();
 
       return hex;
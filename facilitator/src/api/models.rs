@@ -1,28 +1,123 @@
 use serde::{Deserialize, Serialize};
 
+use crate::polkadot::types::{
+    ConfirmationLevel, DryRunFailure, PaymentAsset, SettlementFailure, SettlementResult, SettlementStatus,
+    ValidationParams,
+};
+
 #[derive(Debug, Deserialize)]
 pub struct VerifyRequest {
     pub transaction: String,
     pub expected_amount: u128,
     pub expected_recipient: String,
+    #[serde(default)]
+    pub expected_asset: PaymentAsset,
+    pub network: String,
+    /// Nonce the transfer must be batched with as a `System::remark`.
+    #[serde(default)]
+    pub payment_nonce: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
 pub struct VerifyResponse {
     pub valid: bool,
     pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dry_run_failure: Option<DryRunFailure>,
+    /// Original settlement of a transaction that was already settled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub settlement: Option<SettlementResult>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct SettleRequest {
     pub transaction: String,
+    /// When given, the transaction is verified against these requirements
+    /// before it is submitted.
+    #[serde(default)]
+    pub requirements: Option<PaymentRequirements>,
+    #[serde(default)]
+    pub mode: SettleMode,
+    /// How far a synchronous settlement waits before returning. Async and
+    /// streamed settlements always follow the transaction to finality.
+    #[serde(default)]
+    pub confirmation: ConfirmationLevel,
+}
+
+/// What a payment must satisfy, as checked by `/verify`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PaymentRequirements {
+    pub expected_amount: u128,
+    pub expected_recipient: String,
+    #[serde(default)]
+    pub expected_asset: PaymentAsset,
+    pub network: String,
+    #[serde(default)]
+    pub payment_nonce: Option<String>,
+}
+
+impl PaymentRequirements {
+    pub fn validation_params(&self) -> ValidationParams {
+        ValidationParams::new(
+            self.expected_amount,
+            self.expected_recipient.clone(),
+            self.expected_asset.clone(),
+            self.payment_nonce.clone().map(String::into_bytes),
+        )
+    }
+}
+
+/// Whether `/settle` waits for finality or returns once the transaction is
+/// in the pool, leaving progress to `GET /settlements/{id}`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SettleMode {
+    #[default]
+    Sync,
+    Async,
+}
+
+/// How a `/settle` request ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SettleOutcome {
+    Settled,
+    /// Settled by an earlier request.
+    Replayed,
+    /// Not confirmed within the settlement timeout; still being followed.
+    Pending,
+    /// Rejected by verification and never submitted.
+    VerificationFailed,
+    SettlementFailed,
 }
 
 #[derive(Debug, Serialize)]
 pub struct SettleResponse {
+    pub outcome: SettleOutcome,
     pub settled: bool,
+    /// The transaction was settled by an earlier request and `settlement` is
+    /// that original result.
+    pub replayed: bool,
     pub transaction_hash: Option<String>,
     pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub settlement: Option<SettlementResult>,
+    /// Set when the settlement was released before finality or timed out and
+    /// is still followed; see `GET /settlements/{id}`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub settlement_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub settlement_failure: Option<SettlementFailure>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dry_run_failure: Option<DryRunFailure>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SettleAcceptedResponse {
+    pub settlement_id: String,
+    pub transaction_hash: String,
+    pub status: SettlementStatus,
+    pub status_url: String,
 }
 
 #[derive(Debug, Serialize)]
@@ -1,23 +1,47 @@
 use axum::{
-    extract::State,
-    http::StatusCode,
+    body::{self, Body},
+    extract::{Path, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
     Json,
 };
+use futures::stream;
+use std::convert::Infallible;
+use std::future::Future;
 use std::sync::Arc;
-use tracing::{info, warn};
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+use tracing::{error, info, warn};
+use uuid::Uuid;
 
 use crate::{
-    api::models::{HealthResponse, SettleRequest, SettleResponse, VerifyRequest, VerifyResponse},
+    api::models::{
+        HealthResponse, PaymentRequirements, SettleAcceptedResponse, SettleMode, SettleOutcome, SettleRequest,
+        SettleResponse, VerifyRequest, VerifyResponse,
+    },
     config::Config,
-    error::FacilitatorResult,
-    polkadot::PolkadotClient,
+    error::{FacilitatorError, FacilitatorResult},
+    idempotency::{Claim, IdempotencyKeys, IDEMPOTENCY_KEY_HEADER},
+    ledger::{Attempt, AttemptKind, Ledger, LedgerStatus, PaymentDetails},
+    polkadot::{
+        ConfirmationLevel, PendingSettlement, PolkadotClient, SettlementResult, SettlementStatus,
+        SettlementUpdate, SignedTransaction, TransactionData, TransactionValidator, ValidationParams,
+        signature::blake2_256,
+    },
+    settlements::{SettlementRecord, SettlementTracker},
 };
 
 pub type AppState = Arc<AppStateInner>;
 
 pub struct AppStateInner {
     pub config: Config,
-    pub polkadot_client: PolkadotClient,
+    pub polkadot_client: Arc<PolkadotClient>,
+    pub settlements: SettlementTracker,
+    pub ledger: Arc<Ledger>,
+    pub idempotency_keys: IdempotencyKeys,
 }
 
 pub async fn health(State(state): State<AppState>) -> FacilitatorResult<Json<HealthResponse>> {
@@ -37,68 +61,758 @@ pub async fn verify(
     Json(payload): Json<VerifyRequest>,
 ) -> FacilitatorResult<Json<VerifyResponse>> {
     info!(
-        "Verify request - amount={}, recipient={}",
-        payload.expected_amount, payload.expected_recipient
+        "Verify request - amount={}, asset={}, recipient={}, network={}",
+        payload.expected_amount, payload.expected_asset, payload.expected_recipient, payload.network
     );
 
-    match state
-        .polkadot_client
-        .verify_transaction(
-            &payload.transaction,
-            payload.expected_amount,
-            &payload.expected_recipient,
-        )
-        .await
-    {
+    let params = ValidationParams::new(
+        payload.expected_amount,
+        payload.expected_recipient,
+        payload.expected_asset,
+        payload.payment_nonce.map(String::into_bytes),
+    );
+
+    let (result, settled) = match SignedTransaction::from_hex(&payload.transaction) {
+        Ok(mut tx) => {
+            let settled = state.settlements.settled(&tx.tx_hash).await;
+            let result = match &settled {
+                Some(settlement) => Err(FacilitatorError::AlreadySettled(settlement.tx_hash.clone())),
+                None => {
+                    state
+                        .polkadot_client
+                        .verify_transaction(&mut tx, &params, &payload.network)
+                        .await
+                }
+            };
+            record_verification(&state, &mut tx, &payload.network, &result).await;
+            (result, settled)
+        }
+        Err(e) => (Err(e), None),
+    };
+
+    match result {
         Ok(()) => {
             info!("Transaction verified successfully");
             Ok(Json(VerifyResponse {
                 valid: true,
                 message: "Transaction verified successfully".to_string(),
+                dry_run_failure: None,
+                settlement: None,
             }))
         }
         Err(e) => {
             warn!("Transaction verification failed: {}", e);
+            let dry_run_failure = match &e {
+                FacilitatorError::DryRunFailed(failure) => Some(failure.clone()),
+                _ => None,
+            };
             Ok(Json(VerifyResponse {
                 valid: false,
                 message: format!("Verification failed: {}", e),
+                dry_run_failure,
+                settlement: settled,
             }))
         }
     }
 }
 
+async fn record_verification(
+    state: &AppState,
+    tx: &mut SignedTransaction,
+    network: &str,
+    result: &FacilitatorResult<()>,
+) {
+    let (status, detail) = match result {
+        Ok(()) => (LedgerStatus::Verified, None),
+        Err(e) => (LedgerStatus::Rejected, Some(e.to_string())),
+    };
+    let payment = payment_details(state, tx).await;
+    let attempt = Attempt {
+        id: &Uuid::new_v4().to_string(),
+        kind: AttemptKind::Verify,
+        tx_hash: &tx.tx_hash,
+        network,
+        payment,
+    };
+    state.ledger.record(attempt, status, detail);
+}
+
+/// The payment a transaction carries, for the ledger; `None` if it does not
+/// decode. Reuses the extrinsic decoded for the request or kept when it was
+/// verified.
+async fn payment_details(state: &AppState, tx: &mut SignedTransaction) -> Option<PaymentDetails> {
+    let data = state.polkadot_client.transaction_data(tx).await.ok()?;
+    Some(PaymentDetails::from(&data))
+}
+
+/// Start tracking a settle attempt and return its settlement ID.
+async fn track_settlement(state: &AppState, tx: &mut SignedTransaction) -> FacilitatorResult<String> {
+    let payment = payment_details(state, tx).await;
+    state
+        .settlements
+        .create(tx.tx_hash.clone(), &state.config.polkadot_network, payment)
+}
+
+/// Settle a transaction. With an `Idempotency-Key` header the response is
+/// stored, and a retry with the same key gets it back instead of settling
+/// again.
 pub async fn settle(
     State(state): State<AppState>,
+    headers: HeaderMap,
     Json(payload): Json<SettleRequest>,
-) -> FacilitatorResult<(StatusCode, Json<SettleResponse>)> {
-    info!("Settle request for transaction");
+) -> FacilitatorResult<Response> {
+    let key = headers
+        .get(IDEMPOTENCY_KEY_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
 
-    match state
-        .polkadot_client
-        .submit_transaction(&payload.transaction)
+    // Settle in a task of its own, so a caller that disconnects does not
+    // abandon the settlement with its record unresolved and its key claimed.
+    tokio::spawn(settle_with_key(state, key, payload))
         .await
-    {
-        Ok(tx_hash) => {
-            info!("Transaction settled - Hash: {}", tx_hash);
+        .map_err(|e| FacilitatorError::InternalError(format!("Settle task failed: {}", e)))?
+}
+
+async fn settle_with_key(state: AppState, key: Option<String>, payload: SettleRequest) -> FacilitatorResult<Response> {
+    let tx = SignedTransaction::from_hex(&payload.transaction)?;
+    let Some(key) = key.as_deref() else {
+        return settle_transaction(state, tx, payload).await;
+    };
+
+    match state.idempotency_keys.claim(key, &request_fingerprint(&payload)?) {
+        Claim::New => {}
+        Claim::Replay(status, body) => {
+            info!("Returning stored response for idempotency key {}", key);
+            return Ok((status, [(header::CONTENT_TYPE, "application/json")], body).into_response());
+        }
+        Claim::InProgress => return Err(FacilitatorError::IdempotencyKeyInProgress(key.to_string())),
+        Claim::Mismatch => return Err(FacilitatorError::IdempotencyKeyReused(key.to_string())),
+    }
+
+    let (parts, body) = settle_transaction(state.clone(), tx, payload).await.into_response().into_parts();
+    let body = match body::to_bytes(body, usize::MAX).await {
+        Ok(body) => body,
+        Err(e) => {
+            state.idempotency_keys.release(key);
+            return Err(FacilitatorError::InternalError(format!("Failed to read settle response: {}", e)));
+        }
+    };
+
+    // Server errors and conflicts are transient: leave the key free so a
+    // retry is settled afresh.
+    if parts.status.is_server_error() || parts.status == StatusCode::CONFLICT {
+        state.idempotency_keys.release(key);
+    } else {
+        state.idempotency_keys.complete(key, parts.status, body.clone());
+    }
+    Ok(Response::from_parts(parts, Body::from(body)))
+}
+
+/// What a retry under the same idempotency key must repeat: the
+/// transaction and everything that decides how it is settled.
+fn request_fingerprint(payload: &SettleRequest) -> FacilitatorResult<String> {
+    let request = serde_json::to_vec(payload)
+        .map_err(|e| FacilitatorError::InternalError(format!("Failed to encode settle request: {}", e)))?;
+    Ok(format!("0x{}", hex::encode(blake2_256(&request))))
+}
+
+async fn settle_transaction(
+    state: AppState,
+    mut tx: SignedTransaction,
+    payload: SettleRequest,
+) -> FacilitatorResult<Response> {
+    info!(
+        "Settle request for transaction ({:?} mode, {:?} confirmation)",
+        payload.mode, payload.confirmation
+    );
+    if payload.mode == SettleMode::Async {
+        require_finality(payload.confirmation, "async settlement")?;
+    }
+
+    if let Err(response) = prepare_settlement(&state, &mut tx, payload.requirements.as_ref()).await {
+        return Ok(response);
+    }
+
+    match payload.mode {
+        SettleMode::Sync => settle_sync(state, tx, payload.confirmation).await,
+        SettleMode::Async => settle_async(state, tx).await,
+    }
+}
+
+/// Check a transaction before it is settled: a replay is checked against the
+/// requirements it is presented for, a new transaction is verified against
+/// them. Fails with the response to send instead of settling, either the
+/// original settlement of a replay or the verification failure.
+async fn prepare_settlement(
+    state: &AppState,
+    tx: &mut SignedTransaction,
+    requirements: Option<&PaymentRequirements>,
+) -> Result<(), Response> {
+    if let Some(settlement) = state.settlements.settled(&tx.tx_hash).await {
+        if let Some(requirements) = requirements {
+            let result = check_replay(state, tx, &settlement, requirements).await;
+            record_verification(state, tx, &requirements.network, &result).await;
+            if let Err(e) = result {
+                return Err(verification_failed_response(e).into_response());
+            }
+        }
+        info!("Transaction {} already settled, returning the original settlement", tx.tx_hash);
+        return Err(replayed_response(settlement).into_response());
+    }
+
+    if let Some(requirements) = requirements {
+        let result = state
+            .polkadot_client
+            .verify_transaction(tx, &requirements.validation_params(), &requirements.network)
+            .await;
+        record_verification(state, tx, &requirements.network, &result).await;
+        if let Err(e) = result {
+            return Err(verification_failed_response(e).into_response());
+        }
+    }
+    Ok(())
+}
+
+/// Check the payment of an already settled transaction against the
+/// requirements it is presented for now. Chain state is not consulted: the
+/// transaction's nonce and funds were spent by the original settlement.
+async fn check_replay(
+    state: &AppState,
+    tx: &mut SignedTransaction,
+    settlement: &SettlementResult,
+    requirements: &PaymentRequirements,
+) -> FacilitatorResult<()> {
+    let payment = state.polkadot_client.transaction_data(tx).await?;
+    let params = state
+        .polkadot_client
+        .canonical_params(&requirements.validation_params())
+        .await?;
+    validate_replay(settlement, &payment, &params, &requirements.network)
+}
+
+/// A settled payment only satisfies requirements it actually meets, so a
+/// transaction settled once cannot unlock a different or pricier payment.
+fn validate_replay(
+    settlement: &SettlementResult,
+    payment: &TransactionData,
+    params: &ValidationParams,
+    network: &str,
+) -> FacilitatorResult<()> {
+    if !settlement.network.eq_ignore_ascii_case(network) {
+        return Err(FacilitatorError::VerificationFailed(format!(
+            "Payment was settled on {}, not {}",
+            settlement.network, network
+        )));
+    }
+    TransactionValidator::validate(payment, params)
+}
+
+/// Wait up to the settlement timeout for `confirmation`. Settlements released
+/// before finality keep being followed so a retraction or drop is recorded as
+/// a revert; settlements that time out or lose their status are answered as
+/// pending and resolved in the background.
+async fn settle_sync(
+    state: AppState,
+    mut tx: SignedTransaction,
+    confirmation: ConfirmationLevel,
+) -> FacilitatorResult<Response> {
+    let settlement_id = track_settlement(&state, &mut tx).await?;
+
+    let mut pending = match state.polkadot_client.submit(tx).await {
+        Ok(pending) => pending,
+        Err(e) => {
+            state.settlements.fail(&settlement_id, &e);
+            return Ok(settle_response(Err(e), None).into_response());
+        }
+    };
+
+    let followed = tokio::time::timeout(
+        state.config.settlement_timeout(),
+        state.polkadot_client.follow(&mut pending, confirmation, |update| {
+            state.settlements.apply_update(&settlement_id, &update);
+        }),
+    )
+    .await;
+
+    match followed {
+        Ok(Ok(settlement)) if settlement.status == SettlementStatus::Finalized => {
+            Ok(settle_response(Ok(settlement), None).into_response())
+        }
+        Ok(Ok(settlement)) => {
+            state.settlements.release(&settlement_id, &settlement);
+            finish_in_background(state.clone(), pending, settlement_id.clone());
+            Ok(settle_response(Ok(settlement), Some(settlement_id)).into_response())
+        }
+        Ok(Err(e)) if e.proves_failure() => {
+            state.settlements.fail(&settlement_id, &e);
+            Ok(settle_response(Err(e), None).into_response())
+        }
+        Ok(Err(e)) => {
+            warn!(
+                "Lost track of settlement {} ({}): {}; following in the background",
+                settlement_id, pending.tx_hash, e
+            );
+            state.settlements.lose_track(&settlement_id, &e);
+            Ok(pending_in_background(&state, pending, settlement_id))
+        }
+        Err(_) => {
+            warn!(
+                "Settlement {} ({}) not confirmed within {}s, following in the background",
+                settlement_id, pending.tx_hash, state.config.settlement_timeout_secs
+            );
+            state.settlements.time_out(&settlement_id);
+            Ok(pending_in_background(&state, pending, settlement_id))
+        }
+    }
+}
+
+/// Answer a settlement as pending and keep following it in the background.
+fn pending_in_background(state: &AppState, pending: PendingSettlement, settlement_id: String) -> Response {
+    let settlement = state
+        .polkadot_client
+        .unconfirmed_settlement(&pending.tx_hash, SettlementStatus::Pending);
+    finish_in_background(state.clone(), pending, settlement_id.clone());
+    pending_response(settlement, settlement_id).into_response()
+}
+
+/// Follow a settlement already answered to finality in the background.
+fn finish_in_background(state: AppState, mut pending: PendingSettlement, settlement_id: String) {
+    tokio::spawn(async move {
+        let _ = follow_to_finality(&state, &mut pending, &settlement_id, |_| {}).await;
+    });
+}
+
+/// Follow a submitted settlement to finality, recording each update and
+/// passing it to `on_update`. The status stream gets the settlement timeout
+/// to report finality; when it stalls or fails, finalized blocks and the
+/// signer's nonce are checked instead, up to the settlement watch deadline.
+/// Only a dropped or invalid extrinsic, or a failed check of its block,
+/// fails the settlement; otherwise its outcome is left unknown.
+async fn follow_to_finality(
+    state: &AppState,
+    pending: &mut PendingSettlement,
+    settlement_id: &str,
+    mut on_update: impl FnMut(&SettlementUpdate) + Send,
+) -> FacilitatorResult<SettlementResult> {
+    let mut record_update = |update: SettlementUpdate| {
+        state.settlements.apply_update(settlement_id, &update);
+        on_update(&update);
+    };
+
+    let followed = within(
+        state.config.settlement_timeout(),
+        state
+            .polkadot_client
+            .follow(pending, ConfirmationLevel::Finalized, &mut record_update),
+    )
+    .await;
+    let result = match followed {
+        Err(e) if !e.proves_failure() => {
+            warn!(
+                "Lost track of settlement {} ({}): {}; checking finalized blocks",
+                settlement_id, pending.tx_hash, e
+            );
+            state.settlements.lose_track(settlement_id, &e);
+            let remaining = state.config.settlement_watch().saturating_sub(state.config.settlement_timeout());
+            let result = within(remaining, state.polkadot_client.watch_finalized(pending)).await;
+            if let Ok(settlement) = &result {
+                record_update(SettlementUpdate::Finalized {
+                    settlement: settlement.clone(),
+                });
+            }
+            result
+        }
+        result => result,
+    };
+
+    match &result {
+        Ok(settlement) => info!("Settlement {} finalized - Hash: {}", settlement_id, settlement.tx_hash),
+        Err(e) if !e.proves_failure() => {
+            error!("Gave up following settlement {} ({}): {}", settlement_id, pending.tx_hash, e);
+            state.settlements.lose_track(settlement_id, e);
+        }
+        Err(e) => match state.settlements.fail(settlement_id, e) {
+            Some(SettlementStatus::Reverted) => error!(
+                "Settlement {} ({}) was reported before finality but reverted: {}",
+                settlement_id, pending.tx_hash, e
+            ),
+            _ => warn!("Settlement {} failed: {}", settlement_id, e),
+        },
+    }
+    result
+}
+
+/// Wait up to `deadline` for a settlement to be followed; running out of
+/// time leaves its outcome unknown, like an RPC error.
+async fn within(
+    deadline: Duration,
+    follow: impl Future<Output = FacilitatorResult<SettlementResult>>,
+) -> FacilitatorResult<SettlementResult> {
+    tokio::time::timeout(deadline, follow).await.unwrap_or_else(|_| {
+        Err(FacilitatorError::PolkadotRpcError(format!(
+            "No settlement status within {}s",
+            deadline.as_secs()
+        )))
+    })
+}
+
+/// Submit in the background and answer `202 Accepted` once the transaction
+/// is in the pool; failures before that point are reported directly.
+async fn settle_async(state: AppState, mut tx: SignedTransaction) -> FacilitatorResult<Response> {
+    let tx_hash = tx.tx_hash.clone();
+    let settlement_id = track_settlement(&state, &mut tx).await?;
+    let (accepted_tx, accepted_rx) = oneshot::channel();
+
+    let task_state = state.clone();
+    let task_id = settlement_id.clone();
+    tokio::spawn(async move {
+        let mut pending = match task_state.polkadot_client.submit(tx).await {
+            Ok(pending) => pending,
+            Err(e) => {
+                warn!("Settlement {} failed: {}", task_id, e);
+                task_state.settlements.fail(&task_id, &e);
+                let _ = accepted_tx.send(Err(e));
+                return;
+            }
+        };
+
+        let mut accepted_tx = Some(accepted_tx);
+        let result = follow_to_finality(&task_state, &mut pending, &task_id, |_| {
+            if let Some(accepted_tx) = accepted_tx.take() {
+                let _ = accepted_tx.send(Ok(()));
+            }
+        })
+        .await;
+        if let (Err(e), Some(accepted_tx)) = (result, accepted_tx.take()) {
+            let _ = accepted_tx.send(Err(e));
+        }
+    });
+
+    // A stalled status subscription must not hold the request open; the
+    // task keeps following the transaction either way.
+    match tokio::time::timeout(state.config.settlement_timeout(), accepted_rx).await {
+        Ok(Ok(Ok(()))) | Err(_) => {
+            let status = state
+                .settlements
+                .get(&settlement_id)
+                .map(|record| record.status)
+                .ok_or_else(|| FacilitatorError::SettlementNotFound(settlement_id.clone()))?;
+            info!("Settlement {} accepted - Hash: {}", settlement_id, tx_hash);
             Ok((
+                StatusCode::ACCEPTED,
+                Json(SettleAcceptedResponse {
+                    status_url: format!("/settlements/{}", settlement_id),
+                    settlement_id,
+                    transaction_hash: tx_hash,
+                    status,
+                }),
+            )
+                .into_response())
+        }
+        Ok(Ok(Err(e))) => Ok(settle_response(Err(e), None).into_response()),
+        Ok(Err(_)) => {
+            error!("Settlement task for {} ended without a result", settlement_id);
+            Err(FacilitatorError::InternalError(
+                "Settlement task ended unexpectedly".to_string(),
+            ))
+        }
+    }
+}
+
+/// Submit a transaction and stream each status transition as a Server-Sent
+/// Event named after the update, ending with `finalized` or `error`. A
+/// transaction settled before gets a single `replayed` event instead.
+pub async fn settle_stream(
+    State(state): State<AppState>,
+    Json(payload): Json<SettleRequest>,
+) -> FacilitatorResult<Response> {
+    info!("Streaming settle request for transaction");
+    require_finality(payload.confirmation, "/settle/stream")?;
+
+    let mut tx = SignedTransaction::from_hex(&payload.transaction)?;
+    if let Err(response) = prepare_settlement(&state, &mut tx, payload.requirements.as_ref()).await {
+        return Ok(response);
+    }
+
+    let (events_tx, events_rx) = mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        if let Err(e) = stream_settlement(&state, tx, &events_tx).await {
+            let (_, Json(response)) = settle_response(Err(e), None);
+            if let Ok(event) = Event::default().event("error").json_data(&response) {
+                let _ = events_tx.send(event);
+            }
+        }
+    });
+
+    let events = stream::unfold(events_rx, |mut events_rx| async move {
+        events_rx.recv().await.map(|event| (Ok::<_, Infallible>(event), events_rx))
+    });
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()).into_response())
+}
+
+/// Settlements that only report their outcome once final follow the
+/// transaction to finality; refuse an earlier confirmation level rather
+/// than ignore it.
+fn require_finality(confirmation: ConfirmationLevel, settlement: &str) -> FacilitatorResult<()> {
+    if confirmation != ConfirmationLevel::Finalized {
+        return Err(FacilitatorError::InvalidRequest(format!(
+            "{} only supports finalized confirmation, got {:?}",
+            settlement, confirmation
+        )));
+    }
+    Ok(())
+}
+
+/// Settle a checked transaction for `/settle/stream`, sending each status
+/// update as an event.
+async fn stream_settlement(
+    state: &AppState,
+    mut tx: SignedTransaction,
+    events_tx: &mpsc::UnboundedSender<Event>,
+) -> FacilitatorResult<()> {
+    let settlement_id = track_settlement(state, &mut tx).await?;
+    let mut pending = match state.polkadot_client.submit(tx).await {
+        Ok(pending) => pending,
+        Err(e) => {
+            state.settlements.fail(&settlement_id, &e);
+            return Err(e);
+        }
+    };
+    follow_to_finality(state, &mut pending, &settlement_id, |update| {
+        if let Ok(event) = Event::default().event(update.name()).json_data(update) {
+            let _ = events_tx.send(event);
+        }
+    })
+    .await
+    .map(|_| ())
+}
+
+/// A combined verify-and-settle request whose transaction failed verification.
+fn verification_failed_response(e: FacilitatorError) -> (StatusCode, Json<SettleResponse>) {
+    warn!("Transaction verification failed: {}", e);
+    let dry_run_failure = match &e {
+        FacilitatorError::DryRunFailed(failure) => Some(failure.clone()),
+        _ => None,
+    };
+    (
+        StatusCode::UNPROCESSABLE_ENTITY,
+        Json(SettleResponse {
+            outcome: SettleOutcome::VerificationFailed,
+            settled: false,
+            replayed: false,
+            transaction_hash: None,
+            message: format!("Verification failed: {}", e),
+            settlement: None,
+            settlement_id: None,
+            settlement_failure: None,
+            dry_run_failure,
+        }),
+    )
+}
+
+/// The original result of a transaction that was already settled.
+fn replayed_response(settlement: SettlementResult) -> (StatusCode, Json<SettleResponse>) {
+    (
+        StatusCode::OK,
+        Json(SettleResponse {
+            outcome: SettleOutcome::Replayed,
+            settled: true,
+            replayed: true,
+            transaction_hash: Some(settlement.tx_hash.clone()),
+            message: format!("Transaction already settled - Hash: {}", settlement.tx_hash),
+            settlement: Some(settlement),
+            settlement_id: None,
+            settlement_failure: None,
+            dry_run_failure: None,
+        }),
+    )
+}
+
+/// `202 Accepted` for a settlement still unconfirmed at the timeout.
+fn pending_response(settlement: SettlementResult, settlement_id: String) -> (StatusCode, Json<SettleResponse>) {
+    (
+        StatusCode::ACCEPTED,
+        Json(SettleResponse {
+            outcome: SettleOutcome::Pending,
+            settled: false,
+            replayed: false,
+            transaction_hash: Some(settlement.tx_hash.clone()),
+            message: format!(
+                "Settlement not yet confirmed; poll /settlements/{} for the outcome",
+                settlement_id
+            ),
+            settlement: Some(settlement),
+            settlement_id: Some(settlement_id),
+            settlement_failure: None,
+            dry_run_failure: None,
+        }),
+    )
+}
+
+fn settle_response(
+    result: FacilitatorResult<SettlementResult>,
+    settlement_id: Option<String>,
+) -> (StatusCode, Json<SettleResponse>) {
+    match result {
+        Ok(settlement) => {
+            info!("Transaction settled - Hash: {}", settlement.tx_hash);
+            (
                 StatusCode::OK,
                 Json(SettleResponse {
+                    outcome: SettleOutcome::Settled,
                     settled: true,
-                    transaction_hash: Some(tx_hash.clone()),
-                    message: format!("Transaction settled - Hash: {}", tx_hash),
+                    replayed: false,
+                    transaction_hash: Some(settlement.tx_hash.clone()),
+                    message: format!("Transaction settled - Hash: {}", settlement.tx_hash),
+                    settlement: Some(settlement),
+                    settlement_id,
+                    settlement_failure: None,
+                    dry_run_failure: None,
                 }),
-            ))
+            )
         }
         Err(e) => {
             warn!("Transaction settlement failed: {}", e);
-            Ok((
+            let settlement_failure = match &e {
+                FacilitatorError::SettlementFailed(failure) => Some(failure.clone()),
+                _ => None,
+            };
+            (
                 StatusCode::BAD_REQUEST,
                 Json(SettleResponse {
+                    outcome: SettleOutcome::SettlementFailed,
                     settled: false,
+                    replayed: false,
                     transaction_hash: None,
                     message: format!("Settlement failed: {}", e),
+                    settlement: None,
+                    settlement_id: None,
+                    settlement_failure,
+                    dry_run_failure: None,
                 }),
-            ))
+            )
+        }
+    }
+}
+
+pub async fn settlement_status(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> FacilitatorResult<Json<SettlementRecord>> {
+    state
+        .settlements
+        .get(&id)
+        .map(Json)
+        .ok_or(FacilitatorError::SettlementNotFound(id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polkadot::PaymentAsset;
+
+    const RECIPIENT: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+
+    fn settlement() -> SettlementResult {
+        SettlementResult {
+            tx_hash: "0x01".to_string(),
+            block_hash: Some("0x02".to_string()),
+            block_number: Some(7),
+            extrinsic_index: Some(1),
+            extrinsic_id: Some("7-1".to_string()),
+            network: "paseo".to_string(),
+            explorer_url: None,
+            status: SettlementStatus::Finalized,
+        }
+    }
+
+    fn payment() -> TransactionData {
+        TransactionData {
+            from: "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty".to_string(),
+            signer: "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty".to_string(),
+            proxy_type: None,
+            to: RECIPIENT.to_string(),
+            asset: PaymentAsset::Native,
+            amount: 100,
+            remarks: vec![b"nonce".to_vec()],
+            signature: "0x00".to_string(),
+            nonce: 0,
         }
     }
+
+    fn params(amount: u128, recipient: &str, remark: Option<&str>) -> ValidationParams {
+        ValidationParams::new(
+            amount,
+            recipient.to_string(),
+            PaymentAsset::Native,
+            remark.map(|remark| remark.as_bytes().to_vec()),
+        )
+    }
+
+    #[test]
+    fn test_replay_matching_requirements() {
+        assert!(validate_replay(&settlement(), &payment(), &params(100, RECIPIENT, Some("nonce")), "paseo").is_ok());
+        assert!(validate_replay(&settlement(), &payment(), &params(50, RECIPIENT, None), "Paseo").is_ok());
+    }
+
+    #[test]
+    fn test_replay_mismatched_requirements_rejected() {
+        let other = "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty";
+        for (params, network) in [
+            (params(101, RECIPIENT, None), "paseo"),
+            (params(100, other, None), "paseo"),
+            (params(100, RECIPIENT, Some("other")), "paseo"),
+            (params(100, RECIPIENT, None), "westend"),
+        ] {
+            assert!(matches!(
+                validate_replay(&settlement(), &payment(), &params, network),
+                Err(FacilitatorError::VerificationFailed(_))
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stalled_settlement_left_unknown() {
+        let error = within(Duration::from_millis(10), std::future::pending())
+            .await
+            .unwrap_err();
+        assert!(matches!(error, FacilitatorError::PolkadotRpcError(_)));
+        assert!(!error.proves_failure());
+
+        let settled = within(Duration::from_secs(1), async { Ok(settlement()) }).await;
+        assert_eq!(settled.unwrap(), settlement());
+    }
+
+    #[test]
+    fn test_require_finality() {
+        assert!(require_finality(ConfirmationLevel::Finalized, "/settle/stream").is_ok());
+        for confirmation in [ConfirmationLevel::Pool, ConfirmationLevel::BestBlock] {
+            assert!(matches!(
+                require_finality(confirmation, "/settle/stream"),
+                Err(FacilitatorError::InvalidRequest(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_request_fingerprint() {
+        let fingerprint = |request: serde_json::Value| {
+            request_fingerprint(&serde_json::from_value(request).unwrap()).unwrap()
+        };
+        let requirements = serde_json::json!({
+            "expected_amount": 100,
+            "expected_recipient": RECIPIENT,
+            "network": "paseo",
+        });
+
+        let plain = fingerprint(serde_json::json!({ "transaction": "0x01" }));
+        assert_eq!(plain, fingerprint(serde_json::json!({ "transaction": "0x01", "mode": "sync" })));
+        assert_ne!(plain, fingerprint(serde_json::json!({ "transaction": "0x02" })));
+        assert_ne!(plain, fingerprint(serde_json::json!({ "transaction": "0x01", "mode": "async" })));
+        assert_ne!(plain, fingerprint(serde_json::json!({ "transaction": "0x01", "confirmation": "pool" })));
+        assert_ne!(
+            plain,
+            fingerprint(serde_json::json!({ "transaction": "0x01", "requirements": requirements }))
+        );
+    }
 }
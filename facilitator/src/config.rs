@@ -1,5 +1,9 @@
 use anyhow::{Context, Result};
 use std::env;
+use std::time::Duration;
+
+use crate::polkadot::networks::NetworkConfig;
+use crate::polkadot::types::TransactionLimits;
 
 #[derive(Debug, Clone)]
 pub struct Config {
@@ -8,6 +12,17 @@ pub struct Config {
     pub facilitator_host: String,
     pub facilitator_port: u16,
     pub signer_seed: Option<String>,
+    pub max_nonce_gap: u64,
+    pub min_era_blocks_remaining: u64,
+    pub explorer_url_template: Option<String>,
+    pub settlement_timeout_secs: u64,
+    pub settlement_watch_secs: u64,
+    pub settlement_record_ttl_secs: u64,
+    /// SQLite file for the payment ledger; kept in memory when unset.
+    pub ledger_path: Option<String>,
+    pub idempotency_key_ttl_secs: u64,
+    pub verify_cache_ttl_secs: u64,
+    pub health_check_interval_secs: u64,
 }
 
 impl Config {
@@ -26,9 +41,75 @@ impl Config {
                 .parse()
                 .context("FACILITATOR_PORT must be a valid u16")?,
             signer_seed: env::var("SIGNER_SEED").ok(),
+            max_nonce_gap: env::var("MAX_NONCE_GAP")
+                .unwrap_or_else(|_| "16".to_string())
+                .parse()
+                .context("MAX_NONCE_GAP must be a valid u64")?,
+            min_era_blocks_remaining: env::var("MIN_ERA_BLOCKS_REMAINING")
+                .unwrap_or_else(|_| "10".to_string())
+                .parse()
+                .context("MIN_ERA_BLOCKS_REMAINING must be a valid u64")?,
+            explorer_url_template: env::var("EXPLORER_URL_TEMPLATE").ok(),
+            settlement_timeout_secs: env::var("SETTLEMENT_TIMEOUT_SECS")
+                .unwrap_or_else(|_| "60".to_string())
+                .parse()
+                .context("SETTLEMENT_TIMEOUT_SECS must be a valid u64")?,
+            settlement_watch_secs: env::var("SETTLEMENT_WATCH_SECS")
+                .unwrap_or_else(|_| "3600".to_string())
+                .parse()
+                .context("SETTLEMENT_WATCH_SECS must be a valid u64")?,
+            settlement_record_ttl_secs: env::var("SETTLEMENT_RECORD_TTL_SECS")
+                .unwrap_or_else(|_| "86400".to_string())
+                .parse()
+                .context("SETTLEMENT_RECORD_TTL_SECS must be a valid u64")?,
+            ledger_path: env::var("LEDGER_PATH").ok(),
+            idempotency_key_ttl_secs: env::var("IDEMPOTENCY_KEY_TTL_SECS")
+                .unwrap_or_else(|_| "86400".to_string())
+                .parse()
+                .context("IDEMPOTENCY_KEY_TTL_SECS must be a valid u64")?,
+            verify_cache_ttl_secs: env::var("VERIFY_CACHE_TTL_SECS")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse()
+                .context("VERIFY_CACHE_TTL_SECS must be a valid u64")?,
+            health_check_interval_secs: env::var("HEALTH_CHECK_INTERVAL_SECS")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse()
+                .context("HEALTH_CHECK_INTERVAL_SECS must be a valid u64")?,
         })
     }
 
+    pub fn transaction_limits(&self) -> TransactionLimits {
+        TransactionLimits {
+            max_nonce_gap: self.max_nonce_gap,
+            min_era_blocks_remaining: self.min_era_blocks_remaining,
+        }
+    }
+
+    pub fn network_config(&self) -> NetworkConfig {
+        let network_config = NetworkConfig::from_network_name(&self.polkadot_network);
+        match &self.explorer_url_template {
+            Some(template) => network_config.with_explorer_url_template(template.clone()),
+            None => network_config,
+        }
+    }
+
+    /// How long `/settle` waits for the requested confirmation before
+    /// answering with a pending settlement.
+    pub fn settlement_timeout(&self) -> Duration {
+        Duration::from_secs(self.settlement_timeout_secs)
+    }
+
+    /// How long a settlement answered before finality is followed in the
+    /// background before its outcome is left unknown.
+    pub fn settlement_watch(&self) -> Duration {
+        Duration::from_secs(self.settlement_watch_secs)
+    }
+
+    /// How often the connected RPC node is probed for liveness.
+    pub fn health_check_interval(&self) -> Duration {
+        Duration::from_secs(self.health_check_interval_secs)
+    }
+
     pub fn bind_address(&self) -> String {
         format!("{}:{}", self.facilitator_host, self.facilitator_port)
     }
@@ -1,3 +1,4 @@
+use crate::polkadot::types::{DryRunFailure, SettlementFailure};
 use axum::{
     http::StatusCode,
     response::{IntoResponse, Response},
@@ -11,15 +12,48 @@ pub enum FacilitatorError {
     #[error("Invalid transaction: {0}")]
     InvalidTransaction(String),
 
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+
     #[error("Transaction verification failed: {0}")]
     VerificationFailed(String),
 
+    #[error("Insufficient funds: required {required}, available {available}")]
+    InsufficientFunds { required: u128, available: u128 },
+
+    #[error("Dry run rejected transaction: {0}")]
+    DryRunFailed(DryRunFailure),
+
+    #[error("Settlement failed: {0}")]
+    SettlementFailed(SettlementFailure),
+
+    #[error("Transaction dropped: {0}")]
+    TransactionDropped(String),
+
+    #[error("Settlement not found: {0}")]
+    SettlementNotFound(String),
+
+    #[error("Settlement {0} of this transaction is still in progress")]
+    SettlementInProgress(String),
+
+    #[error("Transaction {0} was already settled")]
+    AlreadySettled(String),
+
+    #[error("A request with idempotency key {0} is still in progress")]
+    IdempotencyKeyInProgress(String),
+
+    #[error("Idempotency key {0} was used for a different transaction")]
+    IdempotencyKeyReused(String),
+
     #[error("Transaction submission failed: {0}")]
     SubmissionFailed(String),
 
     #[error("Polkadot RPC error: {0}")]
     PolkadotRpcError(String),
 
+    #[error("Ledger error: {0}")]
+    LedgerError(String),
+
     #[error("Configuration error: {0}")]
     ConfigError(String),
 
@@ -37,9 +71,20 @@ impl IntoResponse for FacilitatorError {
     fn into_response(self) -> Response {
         let (status, error_type) = match &self {
             FacilitatorError::InvalidTransaction(_) => (StatusCode::BAD_REQUEST, "InvalidTransaction"),
+            FacilitatorError::InvalidRequest(_) => (StatusCode::BAD_REQUEST, "InvalidRequest"),
             FacilitatorError::VerificationFailed(_) => (StatusCode::UNPROCESSABLE_ENTITY, "VerificationFailed"),
+            FacilitatorError::InsufficientFunds { .. } => (StatusCode::UNPROCESSABLE_ENTITY, "InsufficientFunds"),
+            FacilitatorError::DryRunFailed(_) => (StatusCode::UNPROCESSABLE_ENTITY, "DryRunFailed"),
+            FacilitatorError::SettlementFailed(_) => (StatusCode::UNPROCESSABLE_ENTITY, "SettlementFailed"),
+            FacilitatorError::TransactionDropped(_) => (StatusCode::UNPROCESSABLE_ENTITY, "TransactionDropped"),
+            FacilitatorError::SettlementNotFound(_) => (StatusCode::NOT_FOUND, "SettlementNotFound"),
+            FacilitatorError::SettlementInProgress(_) => (StatusCode::CONFLICT, "SettlementInProgress"),
+            FacilitatorError::AlreadySettled(_) => (StatusCode::CONFLICT, "AlreadySettled"),
+            FacilitatorError::IdempotencyKeyInProgress(_) => (StatusCode::CONFLICT, "IdempotencyKeyInProgress"),
+            FacilitatorError::IdempotencyKeyReused(_) => (StatusCode::UNPROCESSABLE_ENTITY, "IdempotencyKeyReused"),
             FacilitatorError::SubmissionFailed(_) => (StatusCode::BAD_GATEWAY, "SubmissionFailed"),
             FacilitatorError::PolkadotRpcError(_) => (StatusCode::BAD_GATEWAY, "PolkadotRpcError"),
+            FacilitatorError::LedgerError(_) => (StatusCode::INTERNAL_SERVER_ERROR, "LedgerError"),
             FacilitatorError::ConfigError(_) => (StatusCode::INTERNAL_SERVER_ERROR, "ConfigError"),
             FacilitatorError::InternalError(_) => (StatusCode::INTERNAL_SERVER_ERROR, "InternalError"),
         };
@@ -53,4 +98,16 @@ impl IntoResponse for FacilitatorError {
     }
 }
 
+impl FacilitatorError {
+    /// Whether the error proves a submitted extrinsic will not settle: it
+    /// was dropped or invalid, or its block showed the payment failed. Other
+    /// errors while following it leave the outcome unknown.
+    pub fn proves_failure(&self) -> bool {
+        matches!(
+            self,
+            FacilitatorError::TransactionDropped(_) | FacilitatorError::SettlementFailed(_)
+        )
+    }
+}
+
 pub type FacilitatorResult<T> = Result<T, FacilitatorError>;
@@ -0,0 +1,124 @@
+use axum::body::Bytes;
+use axum::http::StatusCode;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Header carrying a client-chosen key that makes a `/settle` request safe to
+/// retry.
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// What to do with a request carrying an idempotency key.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Claim {
+    /// First request with this key; process it and `complete` the key.
+    New,
+    /// The key was answered before; send the stored response.
+    Replay(StatusCode, Bytes),
+    /// A request with this key is still being processed.
+    InProgress,
+    /// The key was used for a different request.
+    Mismatch,
+}
+
+enum Outcome {
+    InProgress,
+    Done(StatusCode, Bytes),
+}
+
+struct Entry {
+    /// Identifies the request the key was first used for.
+    fingerprint: String,
+    outcome: Outcome,
+    created_at: Instant,
+}
+
+/// Responses to `/settle` requests, keyed by idempotency key.
+pub struct IdempotencyKeys {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, Entry>>,
+}
+
+impl IdempotencyKeys {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Claim `key` for the request identified by `fingerprint`, dropping
+    /// any keys that have expired.
+    pub fn claim(&self, key: &str, fingerprint: &str) -> Claim {
+        let now = Instant::now();
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|_, entry| now.duration_since(entry.created_at) < self.ttl);
+
+        match entries.get(key) {
+            Some(entry) if entry.fingerprint != fingerprint => Claim::Mismatch,
+            Some(Entry {
+                outcome: Outcome::Done(status, body),
+                ..
+            }) => Claim::Replay(*status, body.clone()),
+            Some(_) => Claim::InProgress,
+            None => {
+                entries.insert(
+                    key.to_string(),
+                    Entry {
+                        fingerprint: fingerprint.to_string(),
+                        outcome: Outcome::InProgress,
+                        created_at: now,
+                    },
+                );
+                Claim::New
+            }
+        }
+    }
+
+    /// Store the response to a claimed key.
+    pub fn complete(&self, key: &str, status: StatusCode, body: Bytes) {
+        if let Some(entry) = self.entries.lock().unwrap().get_mut(key) {
+            entry.outcome = Outcome::Done(status, body);
+        }
+    }
+
+    /// Give up a claimed key so a retry is processed afresh.
+    pub fn release(&self, key: &str) {
+        self.entries.lock().unwrap().remove(key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_lifecycle() {
+        let keys = IdempotencyKeys::new(Duration::from_secs(60));
+
+        assert_eq!(keys.claim("key", "0x01"), Claim::New);
+        assert_eq!(keys.claim("key", "0x01"), Claim::InProgress);
+        assert_eq!(keys.claim("key", "0x02"), Claim::Mismatch);
+
+        keys.complete("key", StatusCode::OK, Bytes::from_static(b"{}"));
+        assert_eq!(
+            keys.claim("key", "0x01"),
+            Claim::Replay(StatusCode::OK, Bytes::from_static(b"{}"))
+        );
+    }
+
+    #[test]
+    fn test_released_key() {
+        let keys = IdempotencyKeys::new(Duration::from_secs(60));
+        assert_eq!(keys.claim("key", "0x01"), Claim::New);
+        keys.release("key");
+        assert_eq!(keys.claim("key", "0x01"), Claim::New);
+    }
+
+    #[test]
+    fn test_key_expiry() {
+        let keys = IdempotencyKeys::new(Duration::ZERO);
+        assert_eq!(keys.claim("key", "0x01"), Claim::New);
+        assert_eq!(keys.claim("key", "0x02"), Claim::New);
+    }
+}
@@ -0,0 +1,98 @@
+use std::sync::Mutex;
+
+use super::{LedgerEntry, LedgerStore, StatusTransition};
+use crate::error::{FacilitatorError, FacilitatorResult};
+use crate::polkadot::types::SettlementResult;
+
+/// Ledger kept in process memory; lost on restart.
+#[derive(Default)]
+pub struct MemoryStore {
+    entries: Mutex<Vec<LedgerEntry>>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl LedgerStore for MemoryStore {
+    fn insert(&self, entry: &LedgerEntry) -> FacilitatorResult<()> {
+        let mut entries = self.entries.lock().unwrap();
+        if entries.iter().any(|existing| existing.id == entry.id) {
+            return Err(FacilitatorError::LedgerError(format!("Duplicate attempt {}", entry.id)));
+        }
+        entries.push(entry.clone());
+        Ok(())
+    }
+
+    fn append(&self, id: &str, transition: &StatusTransition) -> FacilitatorResult<()> {
+        let mut entries = self.entries.lock().unwrap();
+        let entry = entries
+            .iter_mut()
+            .find(|entry| entry.id == id)
+            .ok_or_else(|| FacilitatorError::LedgerError(format!("Unknown attempt {}", id)))?;
+        entry.status = transition.status;
+        entry.updated_at = transition.at;
+        entry.transitions.push(transition.clone());
+        Ok(())
+    }
+
+    fn set_settlement(&self, id: &str, settlement: &SettlementResult) -> FacilitatorResult<()> {
+        let mut entries = self.entries.lock().unwrap();
+        let entry = entries
+            .iter_mut()
+            .find(|entry| entry.id == id)
+            .ok_or_else(|| FacilitatorError::LedgerError(format!("Unknown attempt {}", id)))?;
+        entry.settlement = Some(settlement.clone());
+        Ok(())
+    }
+
+    fn get(&self, id: &str) -> FacilitatorResult<Option<LedgerEntry>> {
+        let entries = self.entries.lock().unwrap();
+        Ok(entries.iter().find(|entry| entry.id == id).cloned())
+    }
+
+    fn find_by_tx_hash(&self, tx_hash: &str) -> FacilitatorResult<Vec<LedgerEntry>> {
+        let entries = self.entries.lock().unwrap();
+        Ok(entries.iter().filter(|entry| entry.tx_hash == tx_hash).cloned().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ledger::{AttemptKind, LedgerStatus};
+
+    fn entry(id: &str, tx_hash: &str) -> LedgerEntry {
+        LedgerEntry {
+            id: id.to_string(),
+            kind: AttemptKind::Verify,
+            tx_hash: tx_hash.to_string(),
+            network: "paseo".to_string(),
+            payment: None,
+            status: LedgerStatus::Verified,
+            transitions: vec![StatusTransition {
+                status: LedgerStatus::Verified,
+                detail: None,
+                at: 1,
+            }],
+            settlement: None,
+            created_at: 1,
+            updated_at: 1,
+        }
+    }
+
+    #[test]
+    fn test_find_by_tx_hash() {
+        let store = MemoryStore::new();
+        store.insert(&entry("a", "0x01")).unwrap();
+        store.insert(&entry("b", "0x02")).unwrap();
+        store.insert(&entry("c", "0x01")).unwrap();
+
+        let ids: Vec<_> = store.find_by_tx_hash("0x01").unwrap().into_iter().map(|e| e.id).collect();
+        assert_eq!(ids, ["a", "c"]);
+        assert!(store.insert(&entry("a", "0x03")).is_err());
+        assert!(store.get("missing").unwrap().is_none());
+    }
+}
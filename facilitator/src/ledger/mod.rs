@@ -0,0 +1,416 @@
+//! Audit trail of every verify and settle attempt the facilitator handles.
+
+mod memory;
+mod sqlite;
+
+pub use memory::MemoryStore;
+pub use sqlite::SqliteStore;
+
+use serde::Serialize;
+use std::thread;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::{mpsc, oneshot};
+use tracing::error;
+
+use crate::error::{FacilitatorError, FacilitatorResult};
+use crate::polkadot::types::{PaymentAsset, SettlementResult, SettlementStatus, TransactionData};
+
+/// Whether an attempt came through `/verify` or `/settle`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttemptKind {
+    Verify,
+    Settle,
+}
+
+impl AttemptKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AttemptKind::Verify => "verify",
+            AttemptKind::Settle => "settle",
+        }
+    }
+}
+
+impl std::str::FromStr for AttemptKind {
+    type Err = FacilitatorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "verify" => Ok(AttemptKind::Verify),
+            "settle" => Ok(AttemptKind::Settle),
+            other => Err(FacilitatorError::LedgerError(format!("Unknown attempt kind {:?}", other))),
+        }
+    }
+}
+
+/// Status of an attempt: the verification outcome, or how far a settlement
+/// has progressed on chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LedgerStatus {
+    Verified,
+    Rejected,
+    /// Handed to `/settle`, before the pool reported on it.
+    Submitted,
+    Validated,
+    InBestBlock,
+    Finalized,
+    Failed,
+    Reverted,
+    Pending,
+    Unknown,
+}
+
+impl LedgerStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LedgerStatus::Verified => "verified",
+            LedgerStatus::Rejected => "rejected",
+            LedgerStatus::Submitted => "submitted",
+            LedgerStatus::Validated => "validated",
+            LedgerStatus::InBestBlock => "in_best_block",
+            LedgerStatus::Finalized => "finalized",
+            LedgerStatus::Failed => "failed",
+            LedgerStatus::Reverted => "reverted",
+            LedgerStatus::Pending => "pending",
+            LedgerStatus::Unknown => "unknown",
+        }
+    }
+}
+
+impl std::str::FromStr for LedgerStatus {
+    type Err = FacilitatorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "verified" => Ok(LedgerStatus::Verified),
+            "rejected" => Ok(LedgerStatus::Rejected),
+            "submitted" => Ok(LedgerStatus::Submitted),
+            "validated" => Ok(LedgerStatus::Validated),
+            "in_best_block" => Ok(LedgerStatus::InBestBlock),
+            "finalized" => Ok(LedgerStatus::Finalized),
+            "failed" => Ok(LedgerStatus::Failed),
+            "reverted" => Ok(LedgerStatus::Reverted),
+            "pending" => Ok(LedgerStatus::Pending),
+            "unknown" => Ok(LedgerStatus::Unknown),
+            other => Err(FacilitatorError::LedgerError(format!("Unknown ledger status {:?}", other))),
+        }
+    }
+}
+
+impl From<SettlementStatus> for LedgerStatus {
+    fn from(status: SettlementStatus) -> Self {
+        match status {
+            SettlementStatus::Validated => LedgerStatus::Validated,
+            SettlementStatus::InBestBlock => LedgerStatus::InBestBlock,
+            SettlementStatus::Finalized => LedgerStatus::Finalized,
+            SettlementStatus::Failed => LedgerStatus::Failed,
+            SettlementStatus::Reverted => LedgerStatus::Reverted,
+            SettlementStatus::Pending => LedgerStatus::Pending,
+            SettlementStatus::Unknown => LedgerStatus::Unknown,
+        }
+    }
+}
+
+/// The payment an attempt carried, when its extrinsic could be decoded.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PaymentDetails {
+    pub payer: String,
+    pub recipient: String,
+    pub amount: u128,
+    pub asset: PaymentAsset,
+}
+
+impl From<&TransactionData> for PaymentDetails {
+    fn from(data: &TransactionData) -> Self {
+        Self {
+            payer: data.from.clone(),
+            recipient: data.to.clone(),
+            amount: data.amount,
+            asset: data.asset.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StatusTransition {
+    pub status: LedgerStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// Unix time in milliseconds.
+    pub at: u64,
+}
+
+/// One verify or settle attempt with its full status history.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LedgerEntry {
+    pub id: String,
+    pub kind: AttemptKind,
+    pub tx_hash: String,
+    pub network: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment: Option<PaymentDetails>,
+    /// Latest status, the last of `transitions`.
+    pub status: LedgerStatus,
+    pub transitions: Vec<StatusTransition>,
+    /// Where a settle attempt landed, once it was reported as settled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub settlement: Option<SettlementResult>,
+    pub created_at: u64,
+    pub updated_at: u64,
+}
+
+/// What is known about an attempt when it starts.
+pub struct Attempt<'a> {
+    pub id: &'a str,
+    pub kind: AttemptKind,
+    pub tx_hash: &'a str,
+    pub network: &'a str,
+    pub payment: Option<PaymentDetails>,
+}
+
+/// Storage backend for the ledger, driven from the ledger thread.
+pub trait LedgerStore: Send {
+    /// Store a new attempt along with its initial transitions.
+    fn insert(&self, entry: &LedgerEntry) -> FacilitatorResult<()>;
+
+    /// Append a transition to an attempt and make it the current status.
+    fn append(&self, id: &str, transition: &StatusTransition) -> FacilitatorResult<()>;
+
+    /// Attach the settlement reported for an attempt.
+    fn set_settlement(&self, id: &str, settlement: &SettlementResult) -> FacilitatorResult<()>;
+
+    fn get(&self, id: &str) -> FacilitatorResult<Option<LedgerEntry>>;
+
+    /// All attempts for an extrinsic, oldest first.
+    fn find_by_tx_hash(&self, tx_hash: &str) -> FacilitatorResult<Vec<LedgerEntry>>;
+}
+
+/// A request to the ledger thread.
+enum Command {
+    Insert(LedgerEntry),
+    Append(String, StatusTransition),
+    SetSettlement(String, SettlementResult),
+    Get(String, oneshot::Sender<FacilitatorResult<Option<LedgerEntry>>>),
+    FindByTxHash(String, oneshot::Sender<FacilitatorResult<Vec<LedgerEntry>>>),
+}
+
+/// Records attempts in a [`LedgerStore`]. Storage failures are logged rather
+/// than failing the payment they describe.
+///
+/// The store is only touched from a dedicated thread, so its blocking I/O
+/// never runs on an async worker. Writes are queued without waiting; reads
+/// queue behind them and so see every earlier write.
+pub struct Ledger {
+    commands: mpsc::UnboundedSender<Command>,
+}
+
+impl Ledger {
+    pub fn new(store: impl LedgerStore + 'static) -> Self {
+        let (commands, receiver) = mpsc::unbounded_channel();
+        thread::Builder::new()
+            .name("ledger".to_string())
+            .spawn(move || run(store, receiver))
+            .expect("Failed to spawn ledger thread");
+        Self { commands }
+    }
+
+    pub fn in_memory() -> Self {
+        Self::new(MemoryStore::new())
+    }
+
+    /// SQLite ledger at `path` if one is configured, otherwise in memory.
+    pub fn open(path: Option<&str>) -> FacilitatorResult<Self> {
+        match path {
+            Some(path) => Ok(Self::new(SqliteStore::open(path)?)),
+            None => Ok(Self::in_memory()),
+        }
+    }
+
+    /// Record a new attempt with its initial status.
+    pub fn record(&self, attempt: Attempt<'_>, status: LedgerStatus, detail: Option<String>) {
+        let at = now_millis();
+        self.send(Command::Insert(LedgerEntry {
+            id: attempt.id.to_string(),
+            kind: attempt.kind,
+            tx_hash: attempt.tx_hash.to_string(),
+            network: attempt.network.to_string(),
+            payment: attempt.payment,
+            status,
+            transitions: vec![StatusTransition { status, detail, at }],
+            settlement: None,
+            created_at: at,
+            updated_at: at,
+        }));
+    }
+
+    pub fn transition(&self, id: &str, status: LedgerStatus, detail: Option<String>) {
+        let transition = StatusTransition {
+            status,
+            detail,
+            at: now_millis(),
+        };
+        self.send(Command::Append(id.to_string(), transition));
+    }
+
+    pub fn settle(&self, id: &str, settlement: &SettlementResult) {
+        self.send(Command::SetSettlement(id.to_string(), settlement.clone()));
+    }
+
+    pub async fn get(&self, id: &str) -> FacilitatorResult<Option<LedgerEntry>> {
+        let (reply, response) = oneshot::channel();
+        self.send(Command::Get(id.to_string(), reply));
+        response.await.map_err(|_| ledger_stopped())?
+    }
+
+    /// The settlement already reported for an extrinsic: that of its latest
+    /// settle attempt which has not since failed or reverted.
+    pub async fn settled(&self, tx_hash: &str) -> Option<SettlementResult> {
+        let entries = match self.find_by_tx_hash(tx_hash).await {
+            Ok(entries) => entries,
+            Err(e) => {
+                error!("Failed to look up settlements of {} in ledger: {}", tx_hash, e);
+                return None;
+            }
+        };
+        entries
+            .into_iter()
+            .rev()
+            .filter(|entry| entry.kind == AttemptKind::Settle)
+            .filter(|entry| !matches!(entry.status, LedgerStatus::Failed | LedgerStatus::Reverted))
+            .find_map(|entry| entry.settlement)
+    }
+
+    pub async fn find_by_tx_hash(&self, tx_hash: &str) -> FacilitatorResult<Vec<LedgerEntry>> {
+        let (reply, response) = oneshot::channel();
+        self.send(Command::FindByTxHash(tx_hash.to_string(), reply));
+        response.await.map_err(|_| ledger_stopped())?
+    }
+
+    fn send(&self, command: Command) {
+        if self.commands.send(command).is_err() {
+            error!("Ledger thread has stopped; attempt not recorded");
+        }
+    }
+}
+
+/// Apply commands to the store until every [`Ledger`] handle is dropped.
+fn run(store: impl LedgerStore, mut commands: mpsc::UnboundedReceiver<Command>) {
+    while let Some(command) = commands.blocking_recv() {
+        match command {
+            Command::Insert(entry) => {
+                if let Err(e) = store.insert(&entry) {
+                    error!("Failed to record {} attempt {} in ledger: {}", entry.kind.as_str(), entry.id, e);
+                }
+            }
+            Command::Append(id, transition) => {
+                if let Err(e) = store.append(&id, &transition) {
+                    error!(
+                        "Failed to record {} for attempt {} in ledger: {}",
+                        transition.status.as_str(),
+                        id,
+                        e
+                    );
+                }
+            }
+            Command::SetSettlement(id, settlement) => {
+                if let Err(e) = store.set_settlement(&id, &settlement) {
+                    error!("Failed to record settlement for attempt {} in ledger: {}", id, e);
+                }
+            }
+            Command::Get(id, reply) => {
+                let _ = reply.send(store.get(&id));
+            }
+            Command::FindByTxHash(tx_hash, reply) => {
+                let _ = reply.send(store.find_by_tx_hash(&tx_hash));
+            }
+        }
+    }
+}
+
+fn ledger_stopped() -> FacilitatorError {
+    FacilitatorError::LedgerError("Ledger thread has stopped".to_string())
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_round_trip() {
+        for status in [
+            LedgerStatus::Verified,
+            LedgerStatus::Rejected,
+            LedgerStatus::Submitted,
+            LedgerStatus::Validated,
+            LedgerStatus::InBestBlock,
+            LedgerStatus::Finalized,
+            LedgerStatus::Failed,
+            LedgerStatus::Reverted,
+            LedgerStatus::Pending,
+            LedgerStatus::Unknown,
+        ] {
+            assert_eq!(status.as_str().parse::<LedgerStatus>().unwrap(), status);
+            assert_eq!(serde_json::to_value(status).unwrap(), status.as_str());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ledger_records_transitions() {
+        let ledger = Ledger::in_memory();
+        let attempt = Attempt {
+            id: "a",
+            kind: AttemptKind::Settle,
+            tx_hash: "0x01",
+            network: "paseo",
+            payment: None,
+        };
+        ledger.record(attempt, LedgerStatus::Submitted, None);
+        ledger.transition("a", LedgerStatus::Finalized, Some("0x02".to_string()));
+
+        let entry = ledger.get("a").await.unwrap().unwrap();
+        assert_eq!(entry.status, LedgerStatus::Finalized);
+        assert_eq!(entry.transitions.len(), 2);
+        assert_eq!(entry.transitions[1].detail.as_deref(), Some("0x02"));
+    }
+
+    #[tokio::test]
+    async fn test_settled_skips_failed_attempts() {
+        let ledger = Ledger::in_memory();
+        let settlement = SettlementResult {
+            tx_hash: "0x01".to_string(),
+            block_hash: None,
+            block_number: None,
+            extrinsic_index: None,
+            extrinsic_id: None,
+            network: "paseo".to_string(),
+            explorer_url: None,
+            status: SettlementStatus::Validated,
+        };
+        for (id, kind) in [("verify", AttemptKind::Verify), ("settle", AttemptKind::Settle)] {
+            let attempt = Attempt {
+                id,
+                kind,
+                tx_hash: "0x01",
+                network: "paseo",
+                payment: None,
+            };
+            ledger.record(attempt, LedgerStatus::Submitted, None);
+        }
+        assert_eq!(ledger.settled("0x01").await, None);
+
+        ledger.settle("settle", &settlement);
+        assert_eq!(ledger.settled("0x01").await, Some(settlement));
+
+        ledger.transition("settle", LedgerStatus::Reverted, None);
+        assert_eq!(ledger.settled("0x01").await, None);
+    }
+}
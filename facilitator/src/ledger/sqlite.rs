@@ -0,0 +1,323 @@
+use rusqlite::{params, Connection};
+use std::sync::Mutex;
+
+use super::{LedgerEntry, LedgerStore, PaymentDetails, StatusTransition};
+use crate::error::{FacilitatorError, FacilitatorResult};
+use crate::polkadot::types::SettlementResult;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS payment_attempts (
+        id TEXT PRIMARY KEY,
+        kind TEXT NOT NULL,
+        tx_hash TEXT NOT NULL,
+        network TEXT NOT NULL,
+        payer TEXT,
+        recipient TEXT,
+        amount TEXT,
+        asset TEXT,
+        status TEXT NOT NULL,
+        settlement TEXT,
+        created_at INTEGER NOT NULL,
+        updated_at INTEGER NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS payment_attempts_tx_hash ON payment_attempts (tx_hash);
+    CREATE TABLE IF NOT EXISTS status_transitions (
+        attempt_id TEXT NOT NULL REFERENCES payment_attempts (id),
+        status TEXT NOT NULL,
+        detail TEXT,
+        at INTEGER NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS status_transitions_attempt ON status_transitions (attempt_id);
+";
+
+const SELECT_ATTEMPT: &str = "
+    SELECT id, kind, tx_hash, network, payer, recipient, amount, asset, status, settlement, created_at, updated_at
+    FROM payment_attempts";
+
+/// Ledger persisted in a SQLite database file.
+pub struct SqliteStore {
+    conn: Mutex<Connection>,
+}
+
+/// Columns of a `payment_attempts` row, before parsing.
+type AttemptRow = (
+    String,
+    String,
+    String,
+    String,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    String,
+    Option<String>,
+    i64,
+    i64,
+);
+
+impl SqliteStore {
+    pub fn open(path: &str) -> FacilitatorResult<Self> {
+        Self::from_connection(Connection::open(path).map_err(db_error)?)
+    }
+
+    fn from_connection(conn: Connection) -> FacilitatorResult<Self> {
+        conn.execute_batch(SCHEMA).map_err(db_error)?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    fn query(&self, filter: &str, value: &str) -> FacilitatorResult<Vec<LedgerEntry>> {
+        let conn = self.conn.lock().unwrap();
+        let mut statement = conn
+            .prepare(&format!("{} WHERE {} = ?1 ORDER BY created_at, rowid", SELECT_ATTEMPT, filter))
+            .map_err(db_error)?;
+        let rows = statement
+            .query_map(params![value], |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                    row.get(5)?,
+                    row.get(6)?,
+                    row.get(7)?,
+                    row.get(8)?,
+                    row.get(9)?,
+                    row.get(10)?,
+                    row.get(11)?,
+                ))
+            })
+            .map_err(db_error)?
+            .collect::<Result<Vec<AttemptRow>, _>>()
+            .map_err(db_error)?;
+
+        rows.into_iter().map(|row| load_entry(&conn, row)).collect()
+    }
+}
+
+impl LedgerStore for SqliteStore {
+    fn insert(&self, entry: &LedgerEntry) -> FacilitatorResult<()> {
+        let asset = entry
+            .payment
+            .as_ref()
+            .map(|payment| serde_json::to_string(&payment.asset))
+            .transpose()
+            .map_err(|e| FacilitatorError::LedgerError(e.to_string()))?;
+        let settlement = entry
+            .settlement
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| FacilitatorError::LedgerError(e.to_string()))?;
+
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction().map_err(db_error)?;
+        tx.execute(
+            "INSERT INTO payment_attempts
+                (id, kind, tx_hash, network, payer, recipient, amount, asset, status, settlement, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+            params![
+                entry.id,
+                entry.kind.as_str(),
+                entry.tx_hash,
+                entry.network,
+                entry.payment.as_ref().map(|p| &p.payer),
+                entry.payment.as_ref().map(|p| &p.recipient),
+                entry.payment.as_ref().map(|p| p.amount.to_string()),
+                asset,
+                entry.status.as_str(),
+                settlement,
+                entry.created_at as i64,
+                entry.updated_at as i64,
+            ],
+        )
+        .map_err(db_error)?;
+        for transition in &entry.transitions {
+            insert_transition(&tx, &entry.id, transition)?;
+        }
+        tx.commit().map_err(db_error)
+    }
+
+    fn append(&self, id: &str, transition: &StatusTransition) -> FacilitatorResult<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction().map_err(db_error)?;
+        let updated = tx
+            .execute(
+                "UPDATE payment_attempts SET status = ?2, updated_at = ?3 WHERE id = ?1",
+                params![id, transition.status.as_str(), transition.at as i64],
+            )
+            .map_err(db_error)?;
+        if updated == 0 {
+            return Err(FacilitatorError::LedgerError(format!("Unknown attempt {}", id)));
+        }
+        insert_transition(&tx, id, transition)?;
+        tx.commit().map_err(db_error)
+    }
+
+    fn set_settlement(&self, id: &str, settlement: &SettlementResult) -> FacilitatorResult<()> {
+        let settlement =
+            serde_json::to_string(settlement).map_err(|e| FacilitatorError::LedgerError(e.to_string()))?;
+        let updated = self
+            .conn
+            .lock()
+            .unwrap()
+            .execute(
+                "UPDATE payment_attempts SET settlement = ?2 WHERE id = ?1",
+                params![id, settlement],
+            )
+            .map_err(db_error)?;
+        if updated == 0 {
+            return Err(FacilitatorError::LedgerError(format!("Unknown attempt {}", id)));
+        }
+        Ok(())
+    }
+
+    fn get(&self, id: &str) -> FacilitatorResult<Option<LedgerEntry>> {
+        Ok(self.query("id", id)?.into_iter().next())
+    }
+
+    fn find_by_tx_hash(&self, tx_hash: &str) -> FacilitatorResult<Vec<LedgerEntry>> {
+        self.query("tx_hash", tx_hash)
+    }
+}
+
+fn load_entry(conn: &Connection, row: AttemptRow) -> FacilitatorResult<LedgerEntry> {
+    let (id, kind, tx_hash, network, payer, recipient, amount, asset, status, settlement, created_at, updated_at) =
+        row;
+
+    let payment = match (payer, recipient, amount, asset) {
+        (Some(payer), Some(recipient), Some(amount), Some(asset)) => Some(PaymentDetails {
+            payer,
+            recipient,
+            amount: amount
+                .parse()
+                .map_err(|_| FacilitatorError::LedgerError(format!("Invalid amount {:?}", amount)))?,
+            asset: serde_json::from_str(&asset)
+                .map_err(|e| FacilitatorError::LedgerError(format!("Invalid asset {:?}: {}", asset, e)))?,
+        }),
+        _ => None,
+    };
+
+    let mut statement = conn
+        .prepare("SELECT status, detail, at FROM status_transitions WHERE attempt_id = ?1 ORDER BY rowid")
+        .map_err(db_error)?;
+    let transitions = statement
+        .query_map(params![id], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?, row.get::<_, i64>(2)?))
+        })
+        .map_err(db_error)?
+        .map(|row| {
+            let (status, detail, at) = row.map_err(db_error)?;
+            Ok(StatusTransition {
+                status: status.parse()?,
+                detail,
+                at: at as u64,
+            })
+        })
+        .collect::<FacilitatorResult<Vec<_>>>()?;
+
+    Ok(LedgerEntry {
+        id,
+        kind: kind.parse()?,
+        tx_hash,
+        network,
+        payment,
+        status: status.parse()?,
+        transitions,
+        settlement: settlement
+            .map(|settlement| serde_json::from_str(&settlement))
+            .transpose()
+            .map_err(|e| FacilitatorError::LedgerError(format!("Invalid settlement: {}", e)))?,
+        created_at: created_at as u64,
+        updated_at: updated_at as u64,
+    })
+}
+
+fn insert_transition(conn: &Connection, id: &str, transition: &StatusTransition) -> FacilitatorResult<()> {
+    conn.execute(
+        "INSERT INTO status_transitions (attempt_id, status, detail, at) VALUES (?1, ?2, ?3, ?4)",
+        params![id, transition.status.as_str(), transition.detail, transition.at as i64],
+    )
+    .map(|_| ())
+    .map_err(db_error)
+}
+
+fn db_error(e: rusqlite::Error) -> FacilitatorError {
+    FacilitatorError::LedgerError(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ledger::{AttemptKind, LedgerStatus};
+    use crate::polkadot::types::{PaymentAsset, SettlementStatus};
+
+    fn store() -> SqliteStore {
+        SqliteStore::from_connection(Connection::open_in_memory().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let store = store();
+        let entry = LedgerEntry {
+            id: "a".to_string(),
+            kind: AttemptKind::Settle,
+            tx_hash: "0x01".to_string(),
+            network: "asset-hub-paseo".to_string(),
+            payment: Some(PaymentDetails {
+                payer: "payer".to_string(),
+                recipient: "recipient".to_string(),
+                amount: u128::MAX,
+                asset: PaymentAsset::Asset { id: 1984 },
+            }),
+            status: LedgerStatus::Submitted,
+            transitions: vec![StatusTransition {
+                status: LedgerStatus::Submitted,
+                detail: None,
+                at: 10,
+            }],
+            settlement: None,
+            created_at: 10,
+            updated_at: 10,
+        };
+        store.insert(&entry).unwrap();
+
+        let finalized = StatusTransition {
+            status: LedgerStatus::Finalized,
+            detail: Some("0x02".to_string()),
+            at: 20,
+        };
+        store.append("a", &finalized).unwrap();
+        let settlement = SettlementResult {
+            tx_hash: "0x01".to_string(),
+            block_hash: Some("0x02".to_string()),
+            block_number: Some(7),
+            extrinsic_index: Some(1),
+            extrinsic_id: Some("7-1".to_string()),
+            network: "asset-hub-paseo".to_string(),
+            explorer_url: None,
+            status: SettlementStatus::Finalized,
+        };
+        store.set_settlement("a", &settlement).unwrap();
+
+        let loaded = store.get("a").unwrap().unwrap();
+        assert_eq!(loaded.payment, entry.payment);
+        assert_eq!(loaded.status, LedgerStatus::Finalized);
+        assert_eq!(loaded.updated_at, 20);
+        assert_eq!(loaded.settlement, Some(settlement));
+        assert_eq!(loaded.transitions, vec![entry.transitions[0].clone(), finalized]);
+        assert_eq!(store.find_by_tx_hash("0x01").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_append_unknown_attempt() {
+        let transition = StatusTransition {
+            status: LedgerStatus::Failed,
+            detail: None,
+            at: 1,
+        };
+        assert!(store().append("missing", &transition).is_err());
+    }
+}
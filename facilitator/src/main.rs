@@ -1,7 +1,10 @@
 mod api;
 mod config;
 mod error;
+mod idempotency;
+mod ledger;
 mod polkadot;
+mod settlements;
 
 use anyhow::Result;
 use axum::{
@@ -9,6 +12,7 @@ use axum::{
     Router,
 };
 use std::sync::Arc;
+use std::time::Duration;
 use tower_http::cors::{Any, CorsLayer};
 use tower_http::trace::TraceLayer;
 use tracing::{info, Level};
@@ -17,7 +21,10 @@ use tracing_subscriber::FmtSubscriber;
 use crate::{
     api::{routes::AppStateInner, AppState},
     config::Config,
+    idempotency::IdempotencyKeys,
+    ledger::Ledger,
     polkadot::PolkadotClient,
+    settlements::SettlementTracker,
 };
 
 #[tokio::main]
@@ -29,16 +36,34 @@ async fn main() -> Result<()> {
     let config = Config::from_env()?;
     info!("Configuration loaded: network={}", config.polkadot_network);
 
-    let polkadot_client = PolkadotClient::new(
-        config.polkadot_rpc_url.clone(),
-        config.polkadot_network.clone(),
-        config.signer_seed.clone(),
-    )
-    .await?;
+    let polkadot_client = Arc::new(
+        PolkadotClient::new(
+            config.polkadot_rpc_url.clone(),
+            config.network_config(),
+            config.signer_seed.clone(),
+            config.transaction_limits(),
+        )
+        .await?
+        .with_verified_ttl(Duration::from_secs(config.verify_cache_ttl_secs)),
+    );
+    polkadot_client.spawn_health_monitor(config.health_check_interval());
+    info!("RPC health check every {}s", config.health_check_interval_secs);
+
+    let ledger = Arc::new(Ledger::open(config.ledger_path.as_deref())?);
+    info!(
+        "Payment ledger: {}",
+        config.ledger_path.as_deref().unwrap_or("in memory")
+    );
 
     let state: AppState = Arc::new(AppStateInner {
         config: config.clone(),
         polkadot_client,
+        settlements: SettlementTracker::new(
+            ledger.clone(),
+            Duration::from_secs(config.settlement_record_ttl_secs),
+        ),
+        ledger,
+        idempotency_keys: IdempotencyKeys::new(Duration::from_secs(config.idempotency_key_ttl_secs)),
     });
 
     let app = create_router(state);
@@ -62,6 +87,8 @@ fn create_router(state: AppState) -> Router {
         .route("/health", get(api::routes::health))
         .route("/verify", post(api::routes::verify))
         .route("/settle", post(api::routes::settle))
+        .route("/settle/stream", post(api::routes::settle_stream))
+        .route("/settlements/:id", get(api::routes::settlement_status))
         .layer(cors)
         .layer(TraceLayer::new_for_http())
         .with_state(state)
@@ -1,57 +1,170 @@
 use crate::error::{FacilitatorError, FacilitatorResult};
-use crate::polkadot::networks::{find_healthy_node, NetworkConfig};
-use crate::polkadot::types::{TransactionData, ValidationParams};
+use crate::polkadot::dry_run::{failure_from_apply_result, failure_from_validity};
+use crate::polkadot::events::{check_extrinsic_events, ExtrinsicEvent};
+use crate::polkadot::extrinsic::{as_u128, field, proxy_definitions, DecodedExtrinsic, Era, ProxyCall};
+use crate::polkadot::location::{canonical_location, parse_location};
+use crate::polkadot::networks::{find_failover_node, find_healthy_node, NetworkConfig, RpcNode};
+use crate::polkadot::signature::blake2_256;
+use crate::polkadot::types::{
+    ChainContext, ConfirmationLevel, PaymentAsset, SettlementFailure, SettlementResult, SettlementStatus, SettlementUpdate,
+    TransactionData, TransactionLimits, ValidationParams,
+};
 use crate::polkadot::validator::TransactionValidator;
-use serde_json;
+use crate::polkadot::verified::{VerifiedTransactions, DEFAULT_VERIFIED_TTL};
+use std::future::Future;
 use std::str::FromStr;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::time::Duration;
+use tokio::sync::{Mutex, RwLock};
+use tokio::task::JoinHandle;
 use tracing::{debug, error, info, warn};
+use subxt::backend::legacy::LegacyRpcMethods;
+use subxt::backend::rpc::RpcClient;
+use subxt::backend::{StreamOfResults, TransactionStatus};
+use subxt::ext::codec::{Decode, Encode};
+use subxt::ext::scale_value::{self, Value, ValueDef};
+use subxt::utils::{AccountId32, H256};
 use subxt::{OnlineClient, PolkadotConfig};
 use subxt_signer::sr25519::Keypair;
 
+/// Proxy types whose call filter admits balances and asset transfers. A
+/// filtered call still lets `Proxy::proxy` itself succeed, so the dry run
+/// cannot catch a proxy type that forbids the transfer.
+const TRANSFER_PROXY_TYPES: [&str; 1] = ["Any"];
+
+/// Asset Hub's proxy type for pallet-assets calls. Its filter admits
+/// `Assets` and `Utility` calls but not the `System` remarks batched with a
+/// bound payment.
+const ASSETS_PROXY_TYPE: &str = "Assets";
+
+/// How long the connected node gets to answer a liveness probe.
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How often finalized blocks are scanned for an extrinsic whose status
+/// stream was lost, about one relay chain block.
+const BLOCK_SCAN_INTERVAL: Duration = Duration::from_secs(6);
+
+/// A signed transaction as received by a request: hex-decoded and hashed
+/// once, with its extrinsic decoded at most once and only when needed, so a
+/// transaction settled before can be looked up even if it no longer decodes.
+pub struct SignedTransaction {
+    pub tx_hash: String,
+    bytes: Vec<u8>,
+    extrinsic: Option<DecodedExtrinsic>,
+}
+
+impl SignedTransaction {
+    /// Hex-decode a transaction and hash it: blake2_256 of the encoded
+    /// extrinsic.
+    pub fn from_hex(transaction: &str) -> FacilitatorResult<Self> {
+        let bytes = hex::decode(transaction.trim_start_matches("0x")).map_err(|e| {
+            FacilitatorError::InvalidTransaction(format!("Invalid hex transaction: {}", e))
+        })?;
+        Ok(Self {
+            tx_hash: format!("0x{}", hex::encode(blake2_256(&bytes))),
+            bytes,
+            extrinsic: None,
+        })
+    }
+
+    /// The decoded extrinsic, once [`PolkadotClient::decode`] has run.
+    fn extrinsic(&self) -> FacilitatorResult<&DecodedExtrinsic> {
+        self.extrinsic
+            .as_ref()
+            .ok_or_else(|| FacilitatorError::InternalError("Transaction was not decoded".to_string()))
+    }
+}
+
+/// A transaction submitted to the pool, with the status stream still open.
+pub struct PendingSettlement {
+    pub tx_hash: String,
+    tx_bytes: Vec<u8>,
+    extrinsic: DecodedExtrinsic,
+    progress: StreamOfResults<TransactionStatus<H256>>,
+    /// Last finalized block known not to include the extrinsic.
+    scanned_to: u64,
+    /// Last block the extrinsic's era allows it in, if mortal.
+    last_block: Option<u64>,
+}
+
 pub struct PolkadotClient {
     network: String,
     network_config: NetworkConfig,
     connected: Arc<RwLock<bool>>,
     current_rpc: Arc<RwLock<Option<String>>>,
     api: Arc<RwLock<Option<OnlineClient<PolkadotConfig>>>>,
+    rpc: Arc<RwLock<Option<LegacyRpcMethods<PolkadotConfig>>>>,
+    /// Held while switching nodes, so concurrent failures fail over once.
+    reconnecting: Mutex<()>,
     signer: Option<Keypair>,
+    limits: TransactionLimits,
+    verified: VerifiedTransactions,
 }
 
 impl PolkadotClient {
-    pub async fn new(_rpc_url: String, network: String, _signer_seed: Option<String>) -> FacilitatorResult<Self> {
-        info!("Initializing Polkadot client for network: {}", network);
+    pub async fn new(
+        _rpc_url: String,
+        network_config: NetworkConfig,
+        _signer_seed: Option<String>,
+        limits: TransactionLimits,
+    ) -> FacilitatorResult<Self> {
+        info!("Initializing Polkadot client for network: {}", network_config.id);
         info!("Mode: Broadcast only (signing done in frontend)");
-
-        let network_config = NetworkConfig::from_network_name(&network);
         info!("Loaded {} RPC endpoints for {}", network_config.nodes.len(), network_config.name);
 
         let client = Self {
-            network,
+            network: network_config.id.clone(),
             network_config,
             connected: Arc::new(RwLock::new(false)),
             current_rpc: Arc::new(RwLock::new(None)),
             api: Arc::new(RwLock::new(None)),
+            rpc: Arc::new(RwLock::new(None)),
+            reconnecting: Mutex::new(()),
             signer: None,
+            limits,
+            verified: VerifiedTransactions::new(DEFAULT_VERIFIED_TTL),
         };
 
         client.connect().await?;
         Ok(client)
     }
 
+    /// Remember verified transactions for `ttl`, so settling one soon after
+    /// verifying it skips repeating the checks.
+    pub fn with_verified_ttl(mut self, ttl: std::time::Duration) -> Self {
+        self.verified = VerifiedTransactions::new(ttl);
+        self
+    }
+
     async fn connect(&self) -> FacilitatorResult<()> {
         // Find a healthy node
         let node = find_healthy_node(&self.network_config).await
             .ok_or_else(|| FacilitatorError::PolkadotRpcError("No healthy RPC nodes available".to_string()))?;
 
+        self.connect_to(&node).await
+    }
+
+    async fn connect_to(&self, node: &RpcNode) -> FacilitatorResult<()> {
         info!("Connecting to Polkadot RPC: {}", node.url);
 
-        let api = OnlineClient::<PolkadotConfig>::from_url(&node.url)
+        let rpc_client = RpcClient::from_url(&node.url)
             .await
             .map_err(|e| FacilitatorError::PolkadotRpcError(format!("Failed to connect: {}", e)))?;
 
+        let api = OnlineClient::<PolkadotConfig>::from_rpc_client(rpc_client.clone())
+            .await
+            .map_err(|e| FacilitatorError::PolkadotRpcError(format!("Failed to connect: {}", e)))?;
+
+        let genesis_hash = format!("0x{}", hex::encode(api.genesis_hash().0));
+        if !genesis_hash.eq_ignore_ascii_case(&self.network_config.genesis_hash) {
+            return Err(FacilitatorError::ConfigError(format!(
+                "RPC node {} has genesis hash {}, expected {} for {}",
+                node.url, genesis_hash, self.network_config.genesis_hash, self.network_config.name
+            )));
+        }
+
         *self.api.write().await = Some(api);
+        *self.rpc.write().await = Some(LegacyRpcMethods::new(rpc_client));
         *self.current_rpc.write().await = Some(node.url.clone());
         *self.connected.write().await = true;
         info!("Successfully connected to Polkadot network via {}", node.name);
@@ -65,146 +178,1049 @@ impl PolkadotClient {
         }
 
         warn!("Connection lost, attempting to reconnect...");
-        self.connect().await
+        let failed = self.current_rpc.read().await.clone();
+        self.fail_over(failed).await
+    }
+
+    /// Switch away from the node at `failed`, unless another caller already
+    /// has. Tries the following nodes in order, falling back to `failed`
+    /// itself if it is the only one answering again.
+    async fn fail_over(&self, failed: Option<String>) -> FacilitatorResult<()> {
+        let _reconnecting = self.reconnecting.lock().await;
+        let current = self.current_rpc.read().await.clone();
+        if current != failed && self.is_connected().await {
+            return Ok(());
+        }
+        *self.connected.write().await = false;
+
+        let node = match &failed {
+            Some(url) => find_failover_node(&self.network_config, url).await,
+            None => find_healthy_node(&self.network_config).await,
+        }
+        .ok_or_else(|| FacilitatorError::PolkadotRpcError("No healthy RPC nodes available".to_string()))?;
+
+        self.connect_to(&node).await
+    }
+
+    /// Whether the connected node answers `system_health` in time.
+    async fn is_alive(&self) -> bool {
+        let rpc = match self.rpc.read().await.clone() {
+            Some(rpc) => rpc,
+            None => return false,
+        };
+
+        match tokio::time::timeout(HEALTH_CHECK_TIMEOUT, rpc.system_health()).await {
+            Ok(Ok(health)) => {
+                debug!("RPC node healthy: {} peers, syncing: {}", health.peers, health.is_syncing);
+                true
+            }
+            Ok(Err(e)) => {
+                warn!("RPC health check failed: {}", e);
+                false
+            }
+            Err(_) => {
+                warn!("RPC health check timed out");
+                false
+            }
+        }
+    }
+
+    /// Probe the connected node every `interval`, clearing the `connected`
+    /// flag and failing over to the next healthy node when it stops
+    /// answering.
+    pub fn spawn_health_monitor(self: &Arc<Self>, interval: Duration) -> JoinHandle<()> {
+        let client = self.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+
+                let current = client.current_rpc.read().await.clone();
+                if client.is_connected().await && client.is_alive().await {
+                    continue;
+                }
+
+                warn!("RPC node {} is not responding, failing over", current.as_deref().unwrap_or("(none)"));
+                if let Err(e) = client.fail_over(current).await {
+                    error!("RPC failover failed: {}", e);
+                }
+            }
+        })
+    }
+
+    /// Run `call` against the current node. If it fails with an RPC error
+    /// and the node no longer answers, fail over and run it once more, so
+    /// requests in flight when a node drops do not fail.
+    async fn with_failover<T, F, Fut>(&self, call: F) -> FacilitatorResult<T>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = FacilitatorResult<T>>,
+    {
+        let failed = self.current_rpc.read().await.clone();
+        match call().await {
+            Err(FacilitatorError::PolkadotRpcError(e)) if !self.is_alive().await => {
+                warn!("RPC call failed on a dead node ({}), retrying after failover", e);
+                self.fail_over(failed).await?;
+                call().await
+            }
+            result => result,
+        }
     }
 
     pub async fn is_connected(&self) -> bool {
         *self.connected.read().await
     }
 
+    /// Decode the payment a transaction carries, without verifying it.
+    pub async fn transaction_data(&self, tx: &mut SignedTransaction) -> FacilitatorResult<TransactionData> {
+        self.decode(tx).await?;
+        tx.extrinsic()?.transaction_data()
+    }
+
+    /// Decode the transaction's extrinsic, unless that already happened.
+    pub async fn decode(&self, tx: &mut SignedTransaction) -> FacilitatorResult<()> {
+        if tx.extrinsic.is_none() {
+            tx.extrinsic = Some(self.decode_extrinsic(tx).await?);
+        }
+        Ok(())
+    }
+
+    /// Decode a transaction's extrinsic, reusing the one kept for it when it
+    /// was verified recently.
+    async fn decode_extrinsic(&self, tx: &SignedTransaction) -> FacilitatorResult<DecodedExtrinsic> {
+        match self.verified.extrinsic(&tx.tx_hash) {
+            Some(extrinsic) => Ok(extrinsic),
+            None => DecodedExtrinsic::decode(&tx.bytes, &self.api_client().await?.metadata()),
+        }
+    }
+
+    /// Requirements with any foreign asset location in the runtime's
+    /// canonical form, for comparing against a decoded payment.
+    pub async fn canonical_params(&self, params: &ValidationParams) -> FacilitatorResult<ValidationParams> {
+        let api = self.api_client().await?;
+        canonical_params(params, &api.metadata())
+    }
+
     pub async fn verify_transaction(
         &self,
-        transaction: &str,
-        expected_amount: u128,
-        expected_recipient: &str,
+        tx: &mut SignedTransaction,
+        params: &ValidationParams,
+        network: &str,
     ) -> FacilitatorResult<()> {
+        if !self.network_config.matches(network) {
+            warn!("Payment for network {} rejected by {} facilitator", network, self.network_config.id);
+            return Err(FacilitatorError::VerificationFailed(format!(
+                "Payment is for network {}, but this facilitator settles on {}",
+                network, self.network_config.id
+            )));
+        }
+
+        if self.verified.is_verified(&tx.tx_hash, params, network) {
+            debug!("Transaction {} verified recently, skipping checks", tx.tx_hash);
+            return Ok(());
+        }
+
         // Ensure we have a healthy connection
         self.ensure_connected().await?;
 
-        let tx_hex = transaction.trim_start_matches("0x");
-        if hex::decode(tx_hex).is_err() {
-            return Err(FacilitatorError::InvalidTransaction(
-                "Invalid hex-encoded transaction".to_string()
-            ));
+        self.decode(tx).await?;
+        let extrinsic = tx.extrinsic()?;
+        self.with_failover(|| self.check_transaction(&tx.bytes, extrinsic, params)).await?;
+        self.verified.insert(tx.tx_hash.clone(), extrinsic.clone(), params.clone(), network.to_string());
+        Ok(())
+    }
+
+    /// Run every verification check that needs the chain.
+    async fn check_transaction(
+        &self,
+        tx_bytes: &[u8],
+        extrinsic: &DecodedExtrinsic,
+        params: &ValidationParams,
+    ) -> FacilitatorResult<()> {
+        let api_guard = self.api.read().await;
+        let api = api_guard.as_ref().ok_or_else(|| {
+            FacilitatorError::PolkadotRpcError("API client not initialized".to_string())
+        })?;
+
+        let metadata = api.metadata();
+
+        let era = extrinsic.era()?;
+        let (best_hash, best_number) = self.best_block().await?;
+        TransactionValidator::validate_mortality(era, best_number, self.limits.min_era_blocks_remaining)?;
+
+        let next_index = self.account_next_index(&extrinsic.signer).await?;
+        TransactionValidator::validate_nonce(extrinsic.nonce()?, next_index, self.limits.max_nonce_gap)?;
+
+        let context = self.chain_context(api, era, best_number).await?;
+        let payload = extrinsic.signed_payload(&metadata, &context)?;
+        // CheckGenesis and CheckSpecVersion carry no extrinsic bytes: the
+        // signer commits to them in the signed payload, so a payment signed
+        // for another chain or runtime only shows up as a bad signature.
+        TransactionValidator::validate_signature(&extrinsic.signature, &extrinsic.signer, &payload)
+            .map_err(|_| {
+                FacilitatorError::VerificationFailed(format!(
+                    "Invalid signature for signer {}; payment must be signed for genesis {} and spec version {}",
+                    extrinsic.signer, self.network_config.genesis_hash, context.spec_version
+                ))
+            })?;
+        debug!("Signature verified for {}", extrinsic.signer);
+
+        let tx_data = extrinsic.transaction_data()?;
+        debug!("Decoded payment: {} -> {} ({})", tx_data.from, tx_data.to, tx_data.amount);
+
+        TransactionValidator::validate(&tx_data, &canonical_params(params, &metadata)?)?;
+
+        if let Some(proxy) = extrinsic.proxy() {
+            self.check_proxy(api, &proxy, &extrinsic.signer, &tx_data, best_hash).await?;
+            debug!("Proxy {} may pay on behalf of {}", extrinsic.signer, proxy.real);
+        }
+
+        let fee = self.check_funds(api, extrinsic, tx_bytes, &tx_data, best_hash).await?;
+        debug!("Estimated fee: {}", fee);
+
+        self.dry_run(api, tx_bytes, best_hash).await?;
+
+        info!("Transaction verified: {} ({}) to {}", tx_data.amount, tx_data.asset, tx_data.to);
+        Ok(())
+    }
+
+    /// Collect the chain values a signer commits to. Only mortal extrinsics
+    /// need an RPC call, to look up the hash of the era's birth block.
+    async fn chain_context(
+        &self,
+        api: &OnlineClient<PolkadotConfig>,
+        era: Era,
+        best_number: u64,
+    ) -> FacilitatorResult<ChainContext> {
+        let genesis_hash = api.genesis_hash().0;
+        let runtime_version = api.runtime_version();
+
+        let checkpoint_hash = match era {
+            Era::Immortal => genesis_hash,
+            Era::Mortal { .. } => {
+                let rpc_guard = self.rpc.read().await;
+                let rpc = rpc_guard.as_ref().ok_or_else(|| {
+                    FacilitatorError::PolkadotRpcError("RPC client not initialized".to_string())
+                })?;
+
+                let birth = era.birth(best_number);
+                rpc.chain_get_block_hash(Some(birth.into()))
+                    .await
+                    .map_err(|e| FacilitatorError::PolkadotRpcError(format!("Failed to fetch block hash: {}", e)))?
+                    .ok_or_else(|| {
+                        FacilitatorError::VerificationFailed(format!("Era birth block {} not found", birth))
+                    })?
+                    .0
+            }
+        };
+
+        Ok(ChainContext {
+            genesis_hash,
+            spec_version: runtime_version.spec_version,
+            transaction_version: runtime_version.transaction_version,
+            checkpoint_hash,
+        })
+    }
+
+    /// Execute the extrinsic against the current best block without
+    /// submitting it, reporting why it would fail if it would.
+    async fn dry_run(
+        &self,
+        api: &OnlineClient<PolkadotConfig>,
+        tx_bytes: &[u8],
+        best_hash: H256,
+    ) -> FacilitatorResult<()> {
+        let metadata = api.metadata();
+
+        // TransactionSource::External, the extrinsic, then the block hash
+        let mut args = 2u8.encode();
+        args.extend_from_slice(tx_bytes);
+        best_hash.encode_to(&mut args);
+        let validity = self
+            .runtime_call(api, "TaggedTransactionQueue", "validate_transaction", &args, best_hash)
+            .await?;
+        if let Some(failure) = failure_from_validity(&validity) {
+            warn!("Dry run: transaction pool would reject transaction: {}", failure);
+            return Err(FacilitatorError::DryRunFailed(failure));
+        }
+
+        let applied = self
+            .runtime_call(api, "BlockBuilder", "apply_extrinsic", tx_bytes, best_hash)
+            .await?;
+        if let Some(failure) = failure_from_apply_result(&applied, &metadata) {
+            warn!("Dry run: transaction would fail on chain: {}", failure);
+            return Err(FacilitatorError::DryRunFailed(failure));
+        }
+
+        debug!("Dry run succeeded at block 0x{}", hex::encode(best_hash.0));
+        Ok(())
+    }
+
+    /// Confirm `delegate` can dispatch the payment for `proxy.real` the way
+    /// `Proxy::proxy` resolves it: the first of the real account's proxy
+    /// definitions naming the delegate (and the forced type, if any) must
+    /// have no announcement delay and a type that admits transfers.
+    async fn check_proxy(
+        &self,
+        api: &OnlineClient<PolkadotConfig>,
+        proxy: &ProxyCall<'_>,
+        delegate: &AccountId32,
+        tx_data: &TransactionData,
+        at: H256,
+    ) -> FacilitatorResult<()> {
+        let address = subxt::dynamic::storage(
+            "Proxy",
+            "Proxies",
+            vec![Value::from_bytes(&proxy.real)],
+        );
+        let proxies = api
+            .storage()
+            .at(at)
+            .fetch(&address)
+            .await
+            .map_err(|e| FacilitatorError::PolkadotRpcError(format!("Failed to fetch proxies: {}", e)))?
+            .map(|proxies| proxies.to_value())
+            .transpose()
+            .map_err(|e| FacilitatorError::PolkadotRpcError(format!("Failed to decode proxies: {}", e)))?;
+
+        let definitions = match &proxies {
+            Some(proxies) => proxy_definitions(proxies)
+                .ok_or_else(|| FacilitatorError::PolkadotRpcError("Invalid proxy data".to_string()))?,
+            None => Vec::new(),
+        };
+
+        let definition = definitions.into_iter().find(|definition| {
+            definition.delegate == *delegate
+                && (proxy.force_proxy_type.is_none() || proxy.force_proxy_type.as_ref() == Some(&definition.proxy_type))
+        });
+
+        let Some(definition) = definition else {
+            warn!("{} is not a proxy of {}", delegate, proxy.real);
+            return Err(FacilitatorError::VerificationFailed(format!(
+                "{} is not a registered proxy of {}",
+                delegate, proxy.real
+            )));
+        };
+        if definition.delay != 0 {
+            return Err(FacilitatorError::VerificationFailed(format!(
+                "Proxy {} of {} has an announcement delay of {} blocks",
+                delegate, proxy.real, definition.delay
+            )));
+        }
+        if !proxy_permits_payment(&definition.proxy_type, tx_data) {
+            return Err(FacilitatorError::VerificationFailed(format!(
+                "Proxy type {} of {} does not permit this payment",
+                definition.proxy_type, delegate
+            )));
         }
+        Ok(())
+    }
 
-        info!("Transaction format validated");
+    /// Confirm the payer's balance covers the transfer and the signer's covers
+    /// the estimated fee and tip, each keeping the existential deposit. They
+    /// are the same account unless the payment is proxied. Asset transfers
+    /// are checked against the asset balance, with fees still paid in the
+    /// native token. Returns the estimated fee.
+    async fn check_funds(
+        &self,
+        api: &OnlineClient<PolkadotConfig>,
+        extrinsic: &DecodedExtrinsic,
+        tx_bytes: &[u8],
+        tx_data: &TransactionData,
+        at: H256,
+    ) -> FacilitatorResult<u128> {
+        let payer = extrinsic.payer();
+        let asset_key = match &tx_data.asset {
+            PaymentAsset::Native => None,
+            PaymentAsset::Asset { id } => Some(("Assets", Value::u128(*id as u128))),
+            PaymentAsset::ForeignAsset { location } => Some(("ForeignAssets", parse_location(location)?)),
+        };
+        let native_amount = match asset_key {
+            None => tx_data.amount,
+            Some((pallet, asset_id)) => {
+                let balance = self.asset_balance(api, pallet, asset_id, &payer, at).await?;
+                if balance < tx_data.amount {
+                    warn!(
+                        "Insufficient {} funds for {}: balance={}, required={}",
+                        tx_data.asset, payer, balance, tx_data.amount
+                    );
+                    return Err(FacilitatorError::InsufficientFunds {
+                        required: tx_data.amount,
+                        available: balance,
+                    });
+                }
+                0
+            }
+        };
+
+        let mut args = tx_bytes.to_vec();
+        (tx_bytes.len() as u32).encode_to(&mut args);
+        let info = self
+            .runtime_call(api, "TransactionPaymentApi", "query_info", &args, at)
+            .await?;
+        let fee = match &info.value {
+            ValueDef::Composite(fields) => field(fields, "partial_fee").and_then(as_u128),
+            _ => None,
+        }
+        .ok_or_else(|| FacilitatorError::PolkadotRpcError("Invalid fee estimate".to_string()))?;
+
+        let existential_deposit = existential_deposit(&api.metadata())?;
+        let charges = fee.saturating_add(extrinsic.tip()?);
+
+        if payer == extrinsic.signer {
+            let required = native_amount
+                .saturating_add(charges)
+                .saturating_add(existential_deposit);
+            self.ensure_free_balance(api, &payer, required, at).await?;
+        } else {
+            if native_amount > 0 {
+                let required = native_amount.saturating_add(existential_deposit);
+                self.ensure_free_balance(api, &payer, required, at).await?;
+            }
+            let required = charges.saturating_add(existential_deposit);
+            self.ensure_free_balance(api, &extrinsic.signer, required, at).await?;
+        }
+
+        Ok(fee)
+    }
+
+    async fn ensure_free_balance(
+        &self,
+        api: &OnlineClient<PolkadotConfig>,
+        account: &AccountId32,
+        required: u128,
+        at: H256,
+    ) -> FacilitatorResult<()> {
+        let free = self.free_balance(api, account, at).await?;
+        if free < required {
+            warn!(
+                "Insufficient funds for {}: free={}, required={}",
+                account, free, required
+            );
+            return Err(FacilitatorError::InsufficientFunds {
+                required,
+                available: free,
+            });
+        }
         Ok(())
     }
 
-    pub async fn submit_transaction(&self, transaction: &str) -> FacilitatorResult<String> {
+    /// Free balance of an account from `System::Account`; missing accounts
+    /// have no balance.
+    async fn free_balance(
+        &self,
+        api: &OnlineClient<PolkadotConfig>,
+        account: &AccountId32,
+        at: H256,
+    ) -> FacilitatorResult<u128> {
+        let Some(account_info) = self.account_info(api, account, at).await? else {
+            return Ok(0);
+        };
+
+        let free = match &account_info.value {
+            ValueDef::Composite(info) => field(info, "data")
+                .and_then(|data| match &data.value {
+                    ValueDef::Composite(data) => field(data, "free").and_then(as_u128),
+                    _ => None,
+                }),
+            _ => None,
+        };
+        free.ok_or_else(|| FacilitatorError::PolkadotRpcError("Invalid account data".to_string()))
+    }
+
+    /// Nonce of an account from `System::Account` at a given block, which
+    /// counts only included transactions; missing accounts have nonce zero.
+    async fn account_nonce(
+        &self,
+        api: &OnlineClient<PolkadotConfig>,
+        account: &AccountId32,
+        at: H256,
+    ) -> FacilitatorResult<u64> {
+        let Some(account_info) = self.account_info(api, account, at).await? else {
+            return Ok(0);
+        };
+
+        match &account_info.value {
+            ValueDef::Composite(info) => field(info, "nonce").and_then(as_u128),
+            _ => None,
+        }
+        .map(|nonce| nonce as u64)
+        .ok_or_else(|| FacilitatorError::PolkadotRpcError("Invalid account data".to_string()))
+    }
+
+    /// An account's `System::Account` entry, if it exists.
+    async fn account_info(
+        &self,
+        api: &OnlineClient<PolkadotConfig>,
+        account: &AccountId32,
+        at: H256,
+    ) -> FacilitatorResult<Option<Value<u32>>> {
+        let address = subxt::dynamic::storage(
+            "System",
+            "Account",
+            vec![Value::from_bytes(account)],
+        );
+        let account_info = api
+            .storage()
+            .at(at)
+            .fetch(&address)
+            .await
+            .map_err(|e| FacilitatorError::PolkadotRpcError(format!("Failed to fetch account: {}", e)))?;
+
+        account_info
+            .map(|account_info| account_info.to_value())
+            .transpose()
+            .map_err(|e| FacilitatorError::PolkadotRpcError(format!("Failed to decode account: {}", e)))
+    }
+
+    /// Balance from the `Account` storage of `Assets` or `ForeignAssets`;
+    /// accounts that never held the asset have no balance.
+    async fn asset_balance(
+        &self,
+        api: &OnlineClient<PolkadotConfig>,
+        pallet: &str,
+        asset_id: Value,
+        account: &AccountId32,
+        at: H256,
+    ) -> FacilitatorResult<u128> {
+        let address = subxt::dynamic::storage(
+            pallet,
+            "Account",
+            vec![asset_id, Value::from_bytes(account)],
+        );
+        let asset_account = api
+            .storage()
+            .at(at)
+            .fetch(&address)
+            .await
+            .map_err(|e| FacilitatorError::PolkadotRpcError(format!("Failed to fetch asset account: {}", e)))?;
+
+        let Some(asset_account) = asset_account else {
+            return Ok(0);
+        };
+        let asset_account = asset_account
+            .to_value()
+            .map_err(|e| FacilitatorError::PolkadotRpcError(format!("Failed to decode asset account: {}", e)))?;
+
+        match &asset_account.value {
+            ValueDef::Composite(fields) => field(fields, "balance").and_then(as_u128),
+            _ => None,
+        }
+        .ok_or_else(|| FacilitatorError::PolkadotRpcError("Invalid asset account data".to_string()))
+    }
+
+    /// Hash and number of the current best block.
+    async fn best_block(&self) -> FacilitatorResult<(H256, u64)> {
+        let rpc_guard = self.rpc.read().await;
+        let rpc = rpc_guard.as_ref().ok_or_else(|| {
+            FacilitatorError::PolkadotRpcError("RPC client not initialized".to_string())
+        })?;
+
+        let hash = rpc
+            .chain_get_block_hash(None)
+            .await
+            .map_err(|e| FacilitatorError::PolkadotRpcError(format!("Failed to fetch best block: {}", e)))?
+            .ok_or_else(|| FacilitatorError::PolkadotRpcError("Best block not found".to_string()))?;
+
+        let header = rpc
+            .chain_get_header(Some(hash))
+            .await
+            .map_err(|e| FacilitatorError::PolkadotRpcError(format!("Failed to fetch best header: {}", e)))?
+            .ok_or_else(|| FacilitatorError::PolkadotRpcError("Best header not found".to_string()))?;
+
+        Ok((hash, header.number as u64))
+    }
+
+    /// Hash and number of the last finalized block.
+    async fn finalized_head(&self) -> FacilitatorResult<(H256, u64)> {
+        let rpc = self.rpc_methods().await?;
+
+        let hash = rpc
+            .chain_get_finalized_head()
+            .await
+            .map_err(|e| FacilitatorError::PolkadotRpcError(format!("Failed to fetch finalized head: {}", e)))?;
+
+        let header = rpc
+            .chain_get_header(Some(hash))
+            .await
+            .map_err(|e| FacilitatorError::PolkadotRpcError(format!("Failed to fetch finalized header: {}", e)))?
+            .ok_or_else(|| FacilitatorError::PolkadotRpcError("Finalized header not found".to_string()))?;
+
+        Ok((hash, header.number as u64))
+    }
+
+    /// Next nonce for an account, including transactions already in the pool.
+    async fn account_next_index(&self, account: &AccountId32) -> FacilitatorResult<u64> {
+        let rpc_guard = self.rpc.read().await;
+        let rpc = rpc_guard.as_ref().ok_or_else(|| {
+            FacilitatorError::PolkadotRpcError("RPC client not initialized".to_string())
+        })?;
+
+        rpc.system_account_next_index(account)
+            .await
+            .map_err(|e| FacilitatorError::PolkadotRpcError(format!("Failed to fetch account nonce: {}", e)))
+    }
+
+    /// Call a runtime API method and decode its result against the metadata.
+    async fn runtime_call(
+        &self,
+        api: &OnlineClient<PolkadotConfig>,
+        trait_name: &str,
+        method_name: &str,
+        args: &[u8],
+        at: H256,
+    ) -> FacilitatorResult<Value<u32>> {
+        let metadata = api.metadata();
+        let output_ty = metadata
+            .runtime_api_trait_by_name(trait_name)
+            .and_then(|t| t.method_by_name(method_name))
+            .map(|m| m.output_ty())
+            .ok_or_else(|| {
+                FacilitatorError::PolkadotRpcError(format!(
+                    "Runtime API {}_{} not available",
+                    trait_name, method_name
+                ))
+            })?;
+
+        let rpc_guard = self.rpc.read().await;
+        let rpc = rpc_guard.as_ref().ok_or_else(|| {
+            FacilitatorError::PolkadotRpcError("RPC client not initialized".to_string())
+        })?;
+
+        let bytes = rpc
+            .state_call(&format!("{}_{}", trait_name, method_name), Some(args), Some(at))
+            .await
+            .map_err(|e| {
+                FacilitatorError::PolkadotRpcError(format!(
+                    "Runtime call {}_{} failed: {}",
+                    trait_name, method_name, e
+                ))
+            })?;
+
+        scale_value::scale::decode_as_type(&mut &bytes[..], output_ty, metadata.types()).map_err(|e| {
+            FacilitatorError::PolkadotRpcError(format!(
+                "Failed to decode {}_{} result: {}",
+                trait_name, method_name, e
+            ))
+        })
+    }
+
+    /// Submit a transaction to the pool without waiting for any status; use
+    /// [`PolkadotClient::follow`] to track it.
+    pub async fn submit(&self, mut tx: SignedTransaction) -> FacilitatorResult<PendingSettlement> {
         info!("Broadcasting signed transaction");
 
         // Ensure we have a healthy connection
         self.ensure_connected().await?;
 
-        let api_guard = self.api.read().await;
-        let api = api_guard.as_ref().ok_or_else(|| {
-            FacilitatorError::PolkadotRpcError("API client not initialized".to_string())
-        })?;
+        let extrinsic = match tx.extrinsic.take() {
+            Some(extrinsic) => extrinsic,
+            None => self.decode_extrinsic(&tx).await?,
+        };
+        let SignedTransaction { tx_hash, bytes: tx_bytes, .. } = tx;
 
-        let tx_hex = transaction.trim_start_matches("0x");
-        let tx_bytes = hex::decode(tx_hex).map_err(|e| {
-            FacilitatorError::InvalidTransaction(format!("Invalid hex transaction: {}", e))
-        })?;
+        let (_, finalized_number) = self.with_failover(|| self.finalized_head()).await?;
+        let (_, best_number) = self.with_failover(|| self.best_block()).await?;
+        let last_block = extrinsic.era()?.last_block(best_number);
 
         info!("Submitting transaction to blockchain");
 
-        use futures::StreamExt;
-        let mut submit_progress = api.backend()
-            .submit_transaction(&tx_bytes)
-            .await
-            .map_err(|e| FacilitatorError::PolkadotRpcError(
-                format!("Failed to submit transaction: {}", e)
-            ))?;
+        let bytes = &tx_bytes;
+        let progress = self
+            .with_failover(|| async move {
+                self.api_client()
+                    .await?
+                    .backend()
+                    .submit_transaction(bytes)
+                    .await
+                    .map_err(|e| FacilitatorError::PolkadotRpcError(
+                        format!("Failed to submit transaction: {}", e)
+                    ))
+            })
+            .await?;
 
         info!("Transaction submitted, waiting for block inclusion");
 
-        use blake2::{Blake2b512, Digest};
-        let tx_hash_bytes = Blake2b512::digest(&tx_bytes);
-        let tx_hash_hex = format!("0x{}", hex::encode(&tx_hash_bytes[..32]));
+        Ok(PendingSettlement {
+            tx_hash,
+            tx_bytes,
+            extrinsic,
+            progress,
+            scanned_to: finalized_number,
+            last_block,
+        })
+    }
 
-        let mut block_hash = String::new();
+    /// Follow a submitted transaction's status until it reaches
+    /// `confirmation`, reporting each status change to `on_update`. The
+    /// pending settlement can be followed again afterwards, e.g. to finality
+    /// after an early release. If the status stream fails or ends early, the
+    /// transaction is followed through finalized blocks instead.
+    pub async fn follow(
+        &self,
+        pending: &mut PendingSettlement,
+        confirmation: ConfirmationLevel,
+        mut on_update: impl FnMut(SettlementUpdate) + Send,
+    ) -> FacilitatorResult<SettlementResult> {
+        let mut finalized_in = None;
 
-        while let Some(status) = submit_progress.next().await {
+        while let Some(status) = pending.progress.next().await {
             match status {
                 Ok(status) => {
                     match status {
                         subxt::backend::TransactionStatus::Validated => {
                             info!("Transaction validated in transaction pool");
+                            on_update(SettlementUpdate::Validated);
+                            if confirmation == ConfirmationLevel::Pool {
+                                return Ok(self.unconfirmed_settlement(&pending.tx_hash, SettlementStatus::Validated));
+                            }
                         }
                         subxt::backend::TransactionStatus::Broadcasted { .. } => {
                             info!("Transaction broadcasted to network");
+                            on_update(SettlementUpdate::Broadcasted);
                         }
                         subxt::backend::TransactionStatus::InBestBlock { hash } => {
                             info!("Transaction included in best block (hash: 0x{})", hex::encode(hash.hash()));
+                            on_update(SettlementUpdate::InBestBlock {
+                                block_hash: format!("0x{}", hex::encode(hash.hash())),
+                            });
+                            if confirmation == ConfirmationLevel::BestBlock {
+                                return self
+                                    .included_settlement(
+                                        &pending.extrinsic,
+                                        &pending.tx_bytes,
+                                        &pending.tx_hash,
+                                        hash.hash(),
+                                        SettlementStatus::InBestBlock,
+                                    )
+                                    .await;
+                            }
                             info!("Waiting for finalization...");
                         }
                         subxt::backend::TransactionStatus::NoLongerInBestBlock => {
                             info!("Transaction no longer in best block, waiting for finalization...");
+                            on_update(SettlementUpdate::NoLongerInBestBlock);
                         }
                         subxt::backend::TransactionStatus::InFinalizedBlock { hash } => {
                             info!("Transaction finalized in block");
-                            block_hash = format!("0x{}", hex::encode(hash.hash()));
+                            finalized_in = Some(hash.hash());
                             break;
                         }
                         subxt::backend::TransactionStatus::Error { message } => {
-                            return Err(FacilitatorError::PolkadotRpcError(
-                                format!("Transaction error: {}", message)
-                            ));
+                            warn!("Transaction status subscription failed: {}", message);
+                            break;
                         }
                         subxt::backend::TransactionStatus::Invalid { message } => {
-                            return Err(FacilitatorError::PolkadotRpcError(
-                                format!("Transaction invalid: {}", message)
+                            return Err(FacilitatorError::TransactionDropped(
+                                format!("invalid: {}", message)
                             ));
                         }
                         subxt::backend::TransactionStatus::Dropped { message } => {
-                            return Err(FacilitatorError::PolkadotRpcError(
-                                format!("Transaction dropped: {}", message)
-                            ));
+                            return Err(FacilitatorError::TransactionDropped(message));
                         }
                     }
                 }
                 Err(e) => {
-                    return Err(FacilitatorError::PolkadotRpcError(
-                        format!("Transaction status error: {}", e)
-                    ));
+                    warn!("Transaction status subscription failed: {}", e);
+                    break;
                 }
             }
         }
 
+        let settlement = match finalized_in {
+            Some(block_hash) => {
+                self.included_settlement(
+                    &pending.extrinsic,
+                    &pending.tx_bytes,
+                    &pending.tx_hash,
+                    block_hash,
+                    SettlementStatus::Finalized,
+                )
+                .await?
+            }
+            None => {
+                warn!("Lost the status of {}, following it through finalized blocks", pending.tx_hash);
+                self.watch_finalized(pending).await?
+            }
+        };
         info!("Transaction confirmed on-chain");
-        info!("Transaction hash: {}", tx_hash_hex);
-        info!("Block hash: {}", block_hash);
-
-        let response = serde_json::json!({
-            "transaction_hash": tx_hash_hex,
-            "block_hash": block_hash,
-            "explorer_url": format!("https://paseo.subscan.io/extrinsic/{}", tx_hash_hex),
-            "network": "paseo",
-            "status": "confirmed"
+        on_update(SettlementUpdate::Finalized {
+            settlement: settlement.clone(),
         });
+        Ok(settlement)
+    }
+
+    /// Follow a submitted extrinsic through finalized blocks once its status
+    /// stream has failed, failing over first if the node died. Resolves when
+    /// the extrinsic is found in a finalized block, and fails only once it
+    /// can no longer be included.
+    pub async fn watch_finalized(&self, pending: &mut PendingSettlement) -> FacilitatorResult<SettlementResult> {
+        loop {
+            if self.recover().await {
+                self.resubmit(&pending.tx_hash, &pending.tx_bytes).await;
+            }
+
+            match self.scan_finalized(pending).await {
+                Ok(Some(block_hash)) => {
+                    match self
+                        .included_settlement(
+                            &pending.extrinsic,
+                            &pending.tx_bytes,
+                            &pending.tx_hash,
+                            block_hash,
+                            SettlementStatus::Finalized,
+                        )
+                        .await
+                    {
+                        Err(FacilitatorError::PolkadotRpcError(e)) => {
+                            warn!("Failed to check settlement of {}: {}", pending.tx_hash, e);
+                        }
+                        result => return result,
+                    }
+                }
+                Ok(None) => {}
+                Err(FacilitatorError::PolkadotRpcError(e)) => {
+                    warn!("Failed to scan finalized blocks for {}: {}", pending.tx_hash, e);
+                }
+                Err(e) => return Err(e),
+            }
 
-        Ok(response.to_string())
+            tokio::time::sleep(BLOCK_SCAN_INTERVAL).await;
+        }
     }
 
-    fn decode_transaction(&self, transaction: &str) -> FacilitatorResult<TransactionData> {
-        let tx_hex = transaction.trim_start_matches("0x");
+    /// Search the finalized blocks not scanned yet for a pending extrinsic,
+    /// returning the block that includes it. Fails once the extrinsic can no
+    /// longer be included: its era has expired, or its nonce was used by
+    /// another transaction.
+    async fn scan_finalized(&self, pending: &mut PendingSettlement) -> FacilitatorResult<Option<H256>> {
+        let api = self.api_client().await?;
+        let rpc = self.rpc_methods().await?;
+        let (head, head_number) = self.finalized_head().await?;
+
+        while pending.scanned_to < head_number {
+            let number = pending.scanned_to + 1;
+            let block_hash = rpc
+                .chain_get_block_hash(Some(number.into()))
+                .await
+                .map_err(|e| FacilitatorError::PolkadotRpcError(format!("Failed to fetch block hash: {}", e)))?
+                .ok_or_else(|| FacilitatorError::PolkadotRpcError(format!("Block {} not found", number)))?;
+            let block = rpc
+                .chain_get_block(Some(block_hash))
+                .await
+                .map_err(|e| FacilitatorError::PolkadotRpcError(format!("Failed to fetch block: {}", e)))?
+                .ok_or_else(|| FacilitatorError::PolkadotRpcError(format!("Block {} not found", number)))?;
 
-        if hex::decode(tx_hex).is_err() {
-            return Err(FacilitatorError::InvalidTransaction(
-                "Invalid hex-encoded transaction".to_string()
-            ));
+            if block.block.extrinsics.iter().any(|ext| ext.0 == pending.tx_bytes) {
+                return Ok(Some(block_hash));
+            }
+            pending.scanned_to = number;
         }
 
-        Ok(TransactionData {
-            from: "signed_transaction".to_string(),
-            to: "will_be_verified_on_chain".to_string(),
-            amount: 0,
-            signature: transaction.to_string(),
-            nonce: 0,
+        if let Some(last_block) = pending.last_block.filter(|last_block| head_number >= *last_block) {
+            return Err(FacilitatorError::TransactionDropped(format!(
+                "era expired at block {} before inclusion",
+                last_block
+            )));
+        }
+
+        let nonce = pending.extrinsic.nonce()?;
+        if self.account_nonce(&api, &pending.extrinsic.signer, head).await? > nonce {
+            return Err(FacilitatorError::TransactionDropped(format!(
+                "nonce {} was used by another transaction",
+                nonce
+            )));
+        }
+        Ok(None)
+    }
+
+    /// Fail over if the connected node stopped answering. Returns whether
+    /// the client switched nodes.
+    async fn recover(&self) -> bool {
+        let failed = self.current_rpc.read().await.clone();
+        if self.is_alive().await {
+            return false;
+        }
+
+        warn!("RPC node {} is not responding, failing over", failed.as_deref().unwrap_or("(none)"));
+        match self.fail_over(failed.clone()).await {
+            Ok(()) => *self.current_rpc.read().await != failed,
+            Err(e) => {
+                error!("RPC failover failed: {}", e);
+                false
+            }
+        }
+    }
+
+    /// Hand a pending extrinsic to the node failed over to, in case the
+    /// failed node never gossiped it. Nodes refuse extrinsics they already
+    /// have or that were included, which is fine.
+    async fn resubmit(&self, tx_hash: &str, tx_bytes: &[u8]) {
+        let result = match self.rpc_methods().await {
+            Ok(rpc) => rpc
+                .author_submit_extrinsic(tx_bytes)
+                .await
+                .map(|_| ())
+                .map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        if let Err(e) = result {
+            debug!("Resubmitting {} was refused: {}", tx_hash, e);
+        }
+    }
+
+    /// Settlement for an extrinsic not yet known to be in a block: accepted
+    /// into the pool, or still unconfirmed when the caller stopped waiting.
+    pub fn unconfirmed_settlement(&self, tx_hash: &str, status: SettlementStatus) -> SettlementResult {
+        SettlementResult {
+            tx_hash: tx_hash.to_string(),
+            block_hash: None,
+            block_number: None,
+            extrinsic_index: None,
+            extrinsic_id: None,
+            network: self.network_config.id.clone(),
+            explorer_url: self.network_config.explorer_url(tx_hash, None),
+            status,
+        }
+    }
+
+    /// Check an included extrinsic against its block's events and describe
+    /// where it landed. Takes the pending settlement's parts rather than the
+    /// settlement itself, whose status stream is not `Sync`.
+    async fn included_settlement(
+        &self,
+        extrinsic: &DecodedExtrinsic,
+        tx_bytes: &[u8],
+        tx_hash: &str,
+        block_hash: H256,
+        status: SettlementStatus,
+    ) -> FacilitatorResult<SettlementResult> {
+        let api = self.api_client().await?;
+        let (block_number, extrinsic_index) = self
+            .check_settlement(&api, extrinsic, tx_bytes, block_hash)
+            .await?;
+        let extrinsic_id = format!("{}-{}", block_number, extrinsic_index);
+
+        info!("Transaction hash: {}", tx_hash);
+        info!("Block hash: 0x{} (extrinsic {})", hex::encode(block_hash.0), extrinsic_id);
+
+        Ok(SettlementResult {
+            tx_hash: tx_hash.to_string(),
+            block_hash: Some(format!("0x{}", hex::encode(block_hash.0))),
+            block_number: Some(block_number),
+            extrinsic_index: Some(extrinsic_index),
+            explorer_url: self.network_config.explorer_url(tx_hash, Some(&extrinsic_id)),
+            extrinsic_id: Some(extrinsic_id),
+            network: self.network_config.id.clone(),
+            status,
         })
     }
+
+    /// A handle on the current API client that does not hold the lock.
+    async fn api_client(&self) -> FacilitatorResult<OnlineClient<PolkadotConfig>> {
+        self.api.read().await.clone().ok_or_else(|| {
+            FacilitatorError::PolkadotRpcError("API client not initialized".to_string())
+        })
+    }
+
+    /// A handle on the current RPC methods that does not hold the lock.
+    async fn rpc_methods(&self) -> FacilitatorResult<LegacyRpcMethods<PolkadotConfig>> {
+        self.rpc.read().await.clone().ok_or_else(|| {
+            FacilitatorError::PolkadotRpcError("RPC client not initialized".to_string())
+        })
+    }
+
+    /// Locate an included extrinsic in its block and check from the block's
+    /// events that it succeeded and moved the payment. Returns the block
+    /// number and the extrinsic's index in the block.
+    async fn check_settlement(
+        &self,
+        api: &OnlineClient<PolkadotConfig>,
+        extrinsic: &DecodedExtrinsic,
+        tx_bytes: &[u8],
+        block_hash: H256,
+    ) -> FacilitatorResult<(u64, u32)> {
+        let block = {
+            let rpc_guard = self.rpc.read().await;
+            let rpc = rpc_guard.as_ref().ok_or_else(|| {
+                FacilitatorError::PolkadotRpcError("RPC client not initialized".to_string())
+            })?;
+            rpc.chain_get_block(Some(block_hash))
+                .await
+                .map_err(|e| FacilitatorError::PolkadotRpcError(format!("Failed to fetch block: {}", e)))?
+                .ok_or_else(|| FacilitatorError::PolkadotRpcError("Block not found".to_string()))?
+        };
+
+        let extrinsic_index = block
+            .block
+            .extrinsics
+            .iter()
+            .position(|ext| ext.0 == tx_bytes)
+            .ok_or(FacilitatorError::SettlementFailed(SettlementFailure::ExtrinsicNotFound))?
+            as u32;
+
+        let events = api
+            .events()
+            .at(block_hash)
+            .await
+            .map_err(|e| FacilitatorError::PolkadotRpcError(format!("Failed to fetch events: {}", e)))?;
+
+        let mut extrinsic_events = Vec::new();
+        for event in events.iter() {
+            let event = event
+                .map_err(|e| FacilitatorError::PolkadotRpcError(format!("Failed to decode event: {}", e)))?;
+            if event.phase() != subxt::events::Phase::ApplyExtrinsic(extrinsic_index) {
+                continue;
+            }
+            extrinsic_events.push(ExtrinsicEvent {
+                pallet: event.pallet_name().to_string(),
+                variant: event.variant_name().to_string(),
+                fields: event
+                    .field_values()
+                    .map_err(|e| FacilitatorError::PolkadotRpcError(format!("Failed to decode event: {}", e)))?,
+            });
+        }
+
+        let block_number = block.block.header.number as u64;
+        let transfer = extrinsic.transfer()?;
+        check_extrinsic_events(&extrinsic_events, &extrinsic.payer(), &transfer, &api.metadata())
+            .map_err(|failure| {
+                warn!("Extrinsic {}-{} did not settle: {}", block_number, extrinsic_index, failure);
+                FacilitatorError::SettlementFailed(failure)
+            })?;
+
+        Ok((block_number, extrinsic_index))
+    }
+}
+
+/// Whether a proxy of `proxy_type` may dispatch the payment's call.
+fn proxy_permits_payment(proxy_type: &str, tx_data: &TransactionData) -> bool {
+    TRANSFER_PROXY_TYPES.contains(&proxy_type)
+        || (proxy_type == ASSETS_PROXY_TYPE
+            && matches!(tx_data.asset, PaymentAsset::Asset { .. })
+            && tx_data.remarks.is_empty())
+}
+
+fn canonical_params(params: &ValidationParams, metadata: &subxt::Metadata) -> FacilitatorResult<ValidationParams> {
+    let mut canonical = params.clone();
+    if let PaymentAsset::ForeignAsset { location } = &canonical.expected_asset {
+        canonical.expected_asset = PaymentAsset::ForeignAsset {
+            location: canonical_location(location, metadata)?,
+        };
+    }
+    Ok(canonical)
+}
+
+fn existential_deposit(metadata: &subxt::Metadata) -> FacilitatorResult<u128> {
+    let constant = metadata
+        .pallet_by_name("Balances")
+        .and_then(|pallet| pallet.constant_by_name("ExistentialDeposit"))
+        .ok_or_else(|| {
+            FacilitatorError::PolkadotRpcError("Balances::ExistentialDeposit not found".to_string())
+        })?;
+
+    u128::decode(&mut constant.value()).map_err(|e| {
+        FacilitatorError::PolkadotRpcError(format!("Invalid existential deposit: {}", e))
+    })
 }
 
 #[cfg(test)]
@@ -215,19 +1231,46 @@ mod tests {
     async fn test_client_creation() {
         let client = PolkadotClient::new(
             "wss://westend-rpc.polkadot.io".to_string(),
-            "westend".to_string(),
+            NetworkConfig::westend(),
             None,
+            TransactionLimits::default(),
         )
         .await;
         assert!(client.is_ok());
     }
 
+    #[test]
+    fn test_proxy_permits_payment() {
+        let mut tx_data = TransactionData {
+            from: "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".to_string(),
+            signer: "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty".to_string(),
+            proxy_type: None,
+            to: "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty".to_string(),
+            asset: PaymentAsset::Asset { id: 1984 },
+            amount: 1,
+            remarks: Vec::new(),
+            signature: "0x00".to_string(),
+            nonce: 0,
+        };
+        assert!(proxy_permits_payment("Any", &tx_data));
+        assert!(proxy_permits_payment("Assets", &tx_data));
+        assert!(!proxy_permits_payment("NonTransfer", &tx_data));
+
+        tx_data.remarks.push(b"nonce".to_vec());
+        assert!(!proxy_permits_payment("Assets", &tx_data));
+
+        tx_data.remarks.clear();
+        tx_data.asset = PaymentAsset::Native;
+        assert!(!proxy_permits_payment("Assets", &tx_data));
+    }
+
     #[tokio::test]
     async fn test_is_connected() {
         let client = PolkadotClient::new(
             "wss://westend-rpc.polkadot.io".to_string(),
-            "westend".to_string(),
+            NetworkConfig::westend(),
             None,
+            TransactionLimits::default(),
         )
         .await
         .unwrap();
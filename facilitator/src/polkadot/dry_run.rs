@@ -0,0 +1,139 @@
+use crate::polkadot::extrinsic::{as_bytes, as_u128, as_variant, field, single_value};
+use crate::polkadot::types::DryRunFailure;
+use subxt::ext::scale_value::{Value, ValueDef};
+use subxt::Metadata;
+
+/// Interpret a `TaggedTransactionQueue_validate_transaction` result
+/// (`Result<ValidTransaction, TransactionValidityError>`).
+pub fn failure_from_validity(result: &Value<u32>) -> Option<DryRunFailure> {
+    match as_variant(result)? {
+        ("Err", error) => Some(validity_error(single_value(error)?)),
+        _ => None,
+    }
+}
+
+/// Interpret a `BlockBuilder_apply_extrinsic` result
+/// (`Result<Result<(), DispatchError>, TransactionValidityError>`).
+pub fn failure_from_apply_result(result: &Value<u32>, metadata: &Metadata) -> Option<DryRunFailure> {
+    match as_variant(result)? {
+        ("Err", error) => Some(validity_error(single_value(error)?)),
+        ("Ok", outcome) => match as_variant(single_value(outcome)?)? {
+            ("Err", error) => Some(dispatch_error(single_value(error)?, metadata)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn validity_error(error: &Value<u32>) -> DryRunFailure {
+    let Some((category, inner)) = as_variant(error) else {
+        return unknown("Unrecognized validity error");
+    };
+    let kind = single_value(inner)
+        .and_then(as_variant)
+        .map(|(name, _)| name)
+        .unwrap_or("Unknown");
+
+    match (category, kind) {
+        ("Invalid", "Payment") => DryRunFailure::InsufficientBalance,
+        ("Invalid", "Stale") => DryRunFailure::StaleNonce,
+        ("Invalid", "Future") => DryRunFailure::FutureNonce,
+        ("Invalid", "AncientBirthBlock") => DryRunFailure::ExpiredEra,
+        ("Invalid", "BadProof") => DryRunFailure::BadProof,
+        ("Invalid", kind) => DryRunFailure::InvalidTransaction {
+            kind: kind.to_string(),
+        },
+        (_, kind) => unknown(kind),
+    }
+}
+
+fn dispatch_error(error: &Value<u32>, metadata: &Metadata) -> DryRunFailure {
+    let (pallet, error) = describe_dispatch_error(error, metadata);
+    match (pallet.as_deref(), error.as_str()) {
+        (Some("Balances"), "InsufficientBalance") | (None, "Token::FundsUnavailable") => {
+            DryRunFailure::InsufficientBalance
+        }
+        (Some("Balances"), "ExistentialDeposit" | "Expendability" | "KeepAlive")
+        | (None, "Token::BelowMinimum" | "Token::NotExpendable" | "Token::OnlyProvider") => {
+            DryRunFailure::ExistentialDeposit
+        }
+        _ => DryRunFailure::DispatchError { pallet, error },
+    }
+}
+
+/// Name a `DispatchError`: module errors resolve to their pallet and error
+/// names, token errors to `Token::<kind>`, anything else to its variant name.
+pub fn describe_dispatch_error(error: &Value<u32>, metadata: &Metadata) -> (Option<String>, String) {
+    let Some((name, inner)) = as_variant(error) else {
+        return (None, "Unrecognized dispatch error".to_string());
+    };
+
+    match name {
+        "Module" => module_error(single_value(inner), metadata)
+            .map(|(pallet, error)| (Some(pallet), error))
+            .unwrap_or((None, "Unknown module error".to_string())),
+        "Token" => {
+            let token_error = single_value(inner)
+                .and_then(as_variant)
+                .map(|(name, _)| name)
+                .unwrap_or("Unknown");
+            (None, format!("Token::{}", token_error))
+        }
+        other => (None, other.to_string()),
+    }
+}
+
+/// Resolve a `ModuleError { index, error }` to its pallet and error names.
+fn module_error(error: Option<&Value<u32>>, metadata: &Metadata) -> Option<(String, String)> {
+    let fields = match &error?.value {
+        ValueDef::Composite(fields) => fields,
+        _ => return None,
+    };
+    let index = u8::try_from(as_u128(field(fields, "index")?)?).ok()?;
+    let error_index = *as_bytes(field(fields, "error")?)?.first()?;
+
+    let pallet = metadata.pallet_by_index(index)?;
+    let variant = pallet.error_variant_by_index(error_index)?;
+    Some((pallet.name().to_string(), variant.name.clone()))
+}
+
+fn unknown(kind: &str) -> DryRunFailure {
+    DryRunFailure::UnknownTransaction {
+        kind: kind.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn invalid(kind: &str) -> Value<u32> {
+        Value::unnamed_variant(
+            "Err",
+            vec![Value::unnamed_variant(
+                "Invalid",
+                vec![Value::unnamed_variant(kind, vec![])],
+            )],
+        )
+        .map_context(|_| 0u32)
+    }
+
+    #[test]
+    fn test_validity_ok() {
+        let result = Value::unnamed_variant("Ok", vec![Value::unnamed_composite(vec![])])
+            .map_context(|_| 0u32);
+        assert_eq!(failure_from_validity(&result), None);
+    }
+
+    #[test]
+    fn test_validity_errors() {
+        assert_eq!(failure_from_validity(&invalid("Payment")), Some(DryRunFailure::InsufficientBalance));
+        assert_eq!(failure_from_validity(&invalid("Stale")), Some(DryRunFailure::StaleNonce));
+        assert_eq!(failure_from_validity(&invalid("Future")), Some(DryRunFailure::FutureNonce));
+        assert_eq!(failure_from_validity(&invalid("AncientBirthBlock")), Some(DryRunFailure::ExpiredEra));
+        assert_eq!(
+            failure_from_validity(&invalid("ExhaustsResources")),
+            Some(DryRunFailure::InvalidTransaction { kind: "ExhaustsResources".to_string() })
+        );
+    }
+}
@@ -0,0 +1,142 @@
+use crate::polkadot::dry_run::describe_dispatch_error;
+use crate::polkadot::extrinsic::{as_account_id, as_u128, field, Transfer};
+use crate::polkadot::types::{PaymentAsset, SettlementFailure};
+use subxt::ext::scale_value::Composite;
+use subxt::utils::AccountId32;
+use subxt::Metadata;
+
+/// An event emitted while applying a single extrinsic.
+#[derive(Debug, Clone)]
+pub struct ExtrinsicEvent {
+    pub pallet: String,
+    pub variant: String,
+    pub fields: Composite<u32>,
+}
+
+/// Check that an extrinsic's events show the payment went through: the
+/// extrinsic succeeded and emitted a transfer from `payer` matching `transfer`.
+pub fn check_extrinsic_events(
+    events: &[ExtrinsicEvent],
+    payer: &AccountId32,
+    transfer: &Transfer,
+    metadata: &Metadata,
+) -> Result<(), SettlementFailure> {
+    let mut succeeded = false;
+    let mut transferred = false;
+
+    for event in events {
+        match (event.pallet.as_str(), event.variant.as_str()) {
+            ("System", "ExtrinsicSuccess") => succeeded = true,
+            ("System", "ExtrinsicFailed") => {
+                let (pallet, error) = field(&event.fields, "dispatch_error")
+                    .map(|error| describe_dispatch_error(error, metadata))
+                    .unwrap_or((None, "Unrecognized dispatch error".to_string()));
+                return Err(SettlementFailure::ExtrinsicFailed { pallet, error });
+            }
+            _ => transferred |= is_matching_transfer(event, payer, transfer),
+        }
+    }
+
+    if !succeeded {
+        return Err(SettlementFailure::ExtrinsicNotFound);
+    }
+    if !transferred {
+        return Err(SettlementFailure::TransferMissing);
+    }
+    Ok(())
+}
+
+/// Whether an event is `Balances::Transfer` or `(Foreign)Assets::Transferred`
+/// moving exactly the expected amount of the expected asset.
+fn is_matching_transfer(event: &ExtrinsicEvent, payer: &AccountId32, transfer: &Transfer) -> bool {
+    let fields = &event.fields;
+    let asset_matches = match (event.pallet.as_str(), event.variant.as_str(), &transfer.asset) {
+        ("Balances", "Transfer", PaymentAsset::Native) => true,
+        ("Assets", "Transferred", PaymentAsset::Asset { id }) => {
+            field(fields, "asset_id").and_then(as_u128) == Some(*id as u128)
+        }
+        ("ForeignAssets", "Transferred", PaymentAsset::ForeignAsset { location }) => field(fields, "asset_id")
+            .is_some_and(|asset_id| asset_id.clone().remove_context().to_string() == *location),
+        _ => false,
+    };
+
+    asset_matches
+        && field(fields, "from").and_then(as_account_id).as_ref() == Some(payer)
+        && field(fields, "to").and_then(as_account_id).as_ref() == Some(&transfer.dest)
+        && field(fields, "amount").and_then(as_u128) == Some(transfer.amount)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use subxt::ext::scale_value::{Value, ValueDef};
+
+    fn account(bytes: [u8; 32]) -> Value<()> {
+        Value::unnamed_composite(vec![Value::from_bytes(bytes)])
+    }
+
+    fn event(pallet: &str, variant: &str, fields: Vec<(&str, Value<()>)>) -> ExtrinsicEvent {
+        let fields = Value::named_composite(fields).map_context(|_| 0u32);
+        let ValueDef::Composite(fields) = fields.value else {
+            unreachable!()
+        };
+        ExtrinsicEvent {
+            pallet: pallet.to_string(),
+            variant: variant.to_string(),
+            fields,
+        }
+    }
+
+    fn native_transfer(dest: [u8; 32], amount: u128) -> Transfer {
+        Transfer {
+            asset: PaymentAsset::Native,
+            dest: AccountId32::from(dest),
+            amount,
+        }
+    }
+
+    #[test]
+    fn test_matching_balances_transfer() {
+        let payer = AccountId32::from([1u8; 32]);
+        let transfer_event = event(
+            "Balances",
+            "Transfer",
+            vec![("from", account([1u8; 32])), ("to", account([2u8; 32])), ("amount", Value::u128(500))],
+        );
+
+        assert!(is_matching_transfer(&transfer_event, &payer, &native_transfer([2u8; 32], 500)));
+        assert!(!is_matching_transfer(&transfer_event, &payer, &native_transfer([2u8; 32], 501)));
+        assert!(!is_matching_transfer(&transfer_event, &payer, &native_transfer([3u8; 32], 500)));
+        assert!(!is_matching_transfer(
+            &transfer_event,
+            &AccountId32::from([9u8; 32]),
+            &native_transfer([2u8; 32], 500)
+        ));
+    }
+
+    #[test]
+    fn test_matching_assets_transfer() {
+        let payer = AccountId32::from([1u8; 32]);
+        let transferred = event(
+            "Assets",
+            "Transferred",
+            vec![
+                ("asset_id", Value::u128(1984)),
+                ("from", account([1u8; 32])),
+                ("to", account([2u8; 32])),
+                ("amount", Value::u128(10)),
+            ],
+        );
+        let mut transfer = Transfer {
+            asset: PaymentAsset::Asset { id: 1984 },
+            dest: AccountId32::from([2u8; 32]),
+            amount: 10,
+        };
+
+        assert!(is_matching_transfer(&transferred, &payer, &transfer));
+        transfer.asset = PaymentAsset::Asset { id: 1337 };
+        assert!(!is_matching_transfer(&transferred, &payer, &transfer));
+        transfer.asset = PaymentAsset::Native;
+        assert!(!is_matching_transfer(&transferred, &payer, &transfer));
+    }
+}
@@ -0,0 +1,838 @@
+use crate::error::{FacilitatorError, FacilitatorResult};
+use crate::polkadot::types::{ChainContext, PaymentAsset, TransactionData};
+use subxt::ext::codec::{Compact, Decode, Encode};
+use subxt::ext::scale_value::{self, Composite, Primitive, Value, ValueDef};
+use subxt::utils::{AccountId32, MultiAddress, MultiSignature};
+use subxt::Metadata;
+
+/// The only extrinsic format version we know how to verify.
+const EXTRINSIC_FORMAT_VERSION: u8 = 4;
+
+/// Balances calls accepted as a payment.
+const BALANCES_TRANSFER_CALLS: [&str; 2] = ["transfer_keep_alive", "transfer_allow_death"];
+
+/// pallet-assets (and ForeignAssets) calls accepted as a payment.
+const ASSETS_TRANSFER_CALLS: [&str; 2] = ["transfer", "transfer_keep_alive"];
+
+/// pallet-utility calls whose inner calls are searched for the payment.
+const UTILITY_BATCH_CALLS: [&str; 3] = ["batch", "batch_all", "force_batch"];
+
+/// System calls that can carry a payment memo.
+const REMARK_CALLS: [&str; 2] = ["remark", "remark_with_event"];
+
+/// pallet-proxy calls that dispatch an inner call on behalf of another account.
+const PROXY_CALLS: [&str; 1] = ["proxy"];
+
+/// A transfer found in an extrinsic's call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transfer {
+    pub asset: PaymentAsset,
+    pub dest: AccountId32,
+    pub amount: u128,
+}
+
+/// A call the signer dispatches on behalf of `real` through `Proxy::proxy`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProxyCall<'a> {
+    pub real: AccountId32,
+    pub force_proxy_type: Option<String>,
+    pub call: &'a Value<u32>,
+}
+
+/// A delegate registered for an account in `Proxy::Proxies` storage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProxyDefinition {
+    pub delegate: AccountId32,
+    pub proxy_type: String,
+    pub delay: u128,
+}
+
+/// Transaction mortality from the `CheckMortality` signed extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Era {
+    Immortal,
+    Mortal { period: u64, phase: u64 },
+}
+
+impl Era {
+    /// Decode the compact two-byte era encoding used by `sp_runtime::generic::Era`.
+    pub fn decode(bytes: &[u8]) -> Option<Self> {
+        match bytes {
+            [0] => Some(Era::Immortal),
+            [low, high] => {
+                let encoded = *low as u64 + ((*high as u64) << 8);
+                let period = 2 << (encoded % (1 << 4));
+                let quantize_factor = (period >> 12).max(1);
+                let phase = (encoded >> 4) * quantize_factor;
+                if period >= 4 && phase < period {
+                    Some(Era::Mortal { period, phase })
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// First block at which an extrinsic with this era is valid, given any
+    /// block number inside its validity window.
+    pub fn birth(&self, current: u64) -> u64 {
+        match self {
+            Era::Immortal => 0,
+            Era::Mortal { period, phase } => {
+                (current.max(*phase) - phase) / period * period + phase
+            }
+        }
+    }
+
+    /// Last block an extrinsic with this era can be included in, given any
+    /// block number inside its validity window; `None` if it never expires.
+    pub fn last_block(&self, current: u64) -> Option<u64> {
+        match self {
+            Era::Immortal => None,
+            Era::Mortal { period, .. } => Some(self.birth(current) + period - 1),
+        }
+    }
+}
+
+/// A signed extension as it appears in the extrinsic, kept as raw bytes so the
+/// signed payload can be rebuilt exactly as the signer produced it.
+#[derive(Debug, Clone)]
+pub struct SignedExtension {
+    pub identifier: String,
+    pub extra: Vec<u8>,
+    pub additional_ty: u32,
+}
+
+/// A signed extrinsic decoded against the connected chain's metadata.
+#[derive(Debug, Clone)]
+pub struct DecodedExtrinsic {
+    pub signer: AccountId32,
+    pub signature: MultiSignature,
+    pub signed_extensions: Vec<SignedExtension>,
+    pub call: Value<u32>,
+    pub call_bytes: Vec<u8>,
+}
+
+impl DecodedExtrinsic {
+    /// Decode a length-prefixed, signed v4 extrinsic.
+    pub fn decode(tx_bytes: &[u8], metadata: &Metadata) -> FacilitatorResult<Self> {
+        let mut cursor = tx_bytes;
+
+        let Compact(len) = Compact::<u32>::decode(&mut cursor)
+            .map_err(|e| invalid(format!("Missing length prefix: {}", e)))?;
+        if len as usize != cursor.len() {
+            return Err(invalid(format!(
+                "Length prefix mismatch: expected {} bytes, got {}",
+                len,
+                cursor.len()
+            )));
+        }
+
+        let version = u8::decode(&mut cursor)
+            .map_err(|e| invalid(format!("Missing version byte: {}", e)))?;
+        if version & 0b1000_0000 == 0 {
+            return Err(invalid("Extrinsic is not signed".to_string()));
+        }
+        if version & 0b0111_1111 != EXTRINSIC_FORMAT_VERSION {
+            return Err(invalid(format!(
+                "Unsupported extrinsic version: {}",
+                version & 0b0111_1111
+            )));
+        }
+
+        let signer = match MultiAddress::<AccountId32, ()>::decode(&mut cursor)
+            .map_err(|e| invalid(format!("Invalid signer address: {}", e)))?
+        {
+            MultiAddress::Id(account) => account,
+            _ => return Err(invalid("Signer must be an AccountId32 address".to_string())),
+        };
+
+        let signature = MultiSignature::decode(&mut cursor)
+            .map_err(|e| invalid(format!("Invalid signature: {}", e)))?;
+
+        let mut signed_extensions = Vec::new();
+        for ext in metadata.extrinsic().signed_extensions() {
+            let start = cursor;
+            scale_value::scale::decode_as_type(&mut cursor, ext.extra_ty(), metadata.types())
+                .map_err(|e| {
+                    invalid(format!("Invalid {} signed extension: {}", ext.identifier(), e))
+                })?;
+            signed_extensions.push(SignedExtension {
+                identifier: ext.identifier().to_string(),
+                extra: start[..start.len() - cursor.len()].to_vec(),
+                additional_ty: ext.additional_ty(),
+            });
+        }
+
+        let call_bytes = cursor.to_vec();
+        let call = scale_value::scale::decode_as_type(
+            &mut cursor,
+            metadata.outer_enums().call_enum_ty(),
+            metadata.types(),
+        )
+        .map_err(|e| invalid(format!("Invalid call data: {}", e)))?;
+
+        if !cursor.is_empty() {
+            return Err(invalid(format!(
+                "{} trailing bytes after call data",
+                cursor.len()
+            )));
+        }
+
+        Ok(Self {
+            signer,
+            signature,
+            signed_extensions,
+            call,
+            call_bytes,
+        })
+    }
+
+    /// Raw bytes of a signed extension's extra data, if the chain uses it.
+    pub fn extension(&self, identifier: &str) -> Option<&[u8]> {
+        self.signed_extensions
+            .iter()
+            .find(|ext| ext.identifier == identifier)
+            .map(|ext| ext.extra.as_slice())
+    }
+
+    /// Account nonce from the `CheckNonce` signed extension.
+    pub fn nonce(&self) -> FacilitatorResult<u64> {
+        let mut extra = self
+            .extension("CheckNonce")
+            .ok_or_else(|| invalid("Missing CheckNonce signed extension".to_string()))?;
+        let Compact(nonce) = Compact::<u64>::decode(&mut extra)
+            .map_err(|e| invalid(format!("Invalid nonce: {}", e)))?;
+        Ok(nonce)
+    }
+
+    /// Tip from `ChargeTransactionPayment` or `ChargeAssetTxPayment`, both of
+    /// which start with a compact-encoded tip.
+    pub fn tip(&self) -> FacilitatorResult<u128> {
+        let extra = self
+            .extension("ChargeTransactionPayment")
+            .or_else(|| self.extension("ChargeAssetTxPayment"));
+        let Some(mut extra) = extra else {
+            return Ok(0);
+        };
+        let Compact(tip) = Compact::<u128>::decode(&mut extra)
+            .map_err(|e| invalid(format!("Invalid tip: {}", e)))?;
+        Ok(tip)
+    }
+
+    /// Mortality from the `CheckMortality` signed extension; chains without it
+    /// only accept immortal extrinsics.
+    pub fn era(&self) -> FacilitatorResult<Era> {
+        match self.extension("CheckMortality") {
+            Some(extra) => Era::decode(extra)
+                .ok_or_else(|| invalid("Invalid CheckMortality era".to_string())),
+            None => Ok(Era::Immortal),
+        }
+    }
+
+    /// Rebuild the payload the signer signed: the call, each signed
+    /// extension's extra data, then each extension's additional signed data.
+    pub fn signed_payload(
+        &self,
+        metadata: &Metadata,
+        context: &ChainContext,
+    ) -> FacilitatorResult<Vec<u8>> {
+        let mut payload = self.call_bytes.clone();
+        for ext in &self.signed_extensions {
+            payload.extend_from_slice(&ext.extra);
+        }
+        for ext in &self.signed_extensions {
+            encode_additional_signed(ext, metadata, context, &mut payload)?;
+        }
+        Ok(payload)
+    }
+
+    /// The proxied call, when the extrinsic is a `Proxy::proxy` call.
+    pub fn proxy(&self) -> Option<ProxyCall<'_>> {
+        find_proxy_call(&self.call)
+    }
+
+    /// The account the payment is drawn from: the proxied account for proxy
+    /// calls, otherwise the signer.
+    pub fn payer(&self) -> AccountId32 {
+        self.proxy().map_or_else(|| self.signer.clone(), |proxy| proxy.real)
+    }
+
+    /// The single transfer this extrinsic pays with.
+    pub fn transfer(&self) -> FacilitatorResult<Transfer> {
+        self.payment().map(|(transfer, _)| transfer)
+    }
+
+    /// Extract the payment described by this extrinsic.
+    pub fn transaction_data(&self) -> FacilitatorResult<TransactionData> {
+        let (transfer, remarks) = self.payment()?;
+
+        Ok(TransactionData {
+            from: self.payer().to_string(),
+            signer: self.signer.to_string(),
+            proxy_type: self.proxy().and_then(|proxy| proxy.force_proxy_type),
+            to: transfer.dest.to_string(),
+            asset: transfer.asset,
+            amount: transfer.amount,
+            remarks,
+            signature: format!("0x{}", hex::encode(signature_bytes(&self.signature))),
+            nonce: self.nonce()?,
+        })
+    }
+
+    /// The transfer and any remarks in the payment call, which is the
+    /// proxied call for proxy extrinsics.
+    fn payment(&self) -> FacilitatorResult<(Transfer, Vec<Vec<u8>>)> {
+        let payment_call = self.proxy().map_or(&self.call, |proxy| proxy.call);
+
+        let mut transfers = Vec::new();
+        let mut remarks = Vec::new();
+        collect_payment_calls(payment_call, &mut transfers, &mut remarks);
+
+        let transfer = match transfers.len() {
+            0 => {
+                return Err(invalid(
+                    "Extrinsic does not contain a Balances or Assets transfer call".to_string(),
+                ))
+            }
+            1 => transfers.remove(0),
+            n => return Err(invalid(format!("Extrinsic contains {} transfers, expected one", n))),
+        };
+        Ok((transfer, remarks))
+    }
+}
+
+fn encode_additional_signed(
+    ext: &SignedExtension,
+    metadata: &Metadata,
+    context: &ChainContext,
+    out: &mut Vec<u8>,
+) -> FacilitatorResult<()> {
+    match ext.identifier.as_str() {
+        "CheckSpecVersion" => context.spec_version.encode_to(out),
+        "CheckTxVersion" => context.transaction_version.encode_to(out),
+        "CheckGenesis" => out.extend_from_slice(&context.genesis_hash),
+        "CheckMortality" => out.extend_from_slice(&context.checkpoint_hash),
+        "CheckMetadataHash" => {
+            // Mode::Disabled signs `None`; enabled mode would require the
+            // merkleized metadata hash, which we do not compute.
+            if ext.extra.first().copied().unwrap_or(0) != 0 {
+                return Err(invalid(
+                    "CheckMetadataHash mode is not supported".to_string(),
+                ));
+            }
+            None::<[u8; 32]>.encode_to(out);
+        }
+        identifier => {
+            // Every other extension must have no additional signed data.
+            scale_value::scale::encode_as_type(
+                &Value::unnamed_composite(Vec::new()),
+                ext.additional_ty,
+                metadata.types(),
+                out,
+            )
+            .map_err(|_| {
+                invalid(format!("Unsupported signed extension: {}", identifier))
+            })?;
+        }
+    }
+    Ok(())
+}
+
+pub fn signature_bytes(signature: &MultiSignature) -> &[u8] {
+    match signature {
+        MultiSignature::Ed25519(sig) => sig,
+        MultiSignature::Sr25519(sig) => sig,
+        MultiSignature::Ecdsa(sig) => sig,
+    }
+}
+
+/// Walk a call, descending into utility batches, collecting every supported
+/// transfer and every system remark.
+fn collect_payment_calls(call: &Value<u32>, transfers: &mut Vec<Transfer>, remarks: &mut Vec<Vec<u8>>) {
+    if let Some(transfer) = find_transfer(call) {
+        transfers.push(transfer);
+        return;
+    }
+
+    let Some((pallet, pallet_call)) = as_variant(call) else {
+        return;
+    };
+    let Some((name, fields)) = single_value(pallet_call).and_then(as_variant) else {
+        return;
+    };
+
+    match pallet {
+        "Utility" if UTILITY_BATCH_CALLS.contains(&name) => {
+            if let Some(ValueDef::Composite(calls)) = field(fields, "calls").map(|c| &c.value) {
+                for inner in calls.values() {
+                    collect_payment_calls(inner, transfers, remarks);
+                }
+            }
+        }
+        "System" if REMARK_CALLS.contains(&name) => {
+            if let Some(remark) = field(fields, "remark").and_then(as_bytes) {
+                remarks.push(remark);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Unwrap a `Proxy::proxy { real, force_proxy_type, call }` call.
+pub fn find_proxy_call(call: &Value<u32>) -> Option<ProxyCall<'_>> {
+    let (pallet, pallet_call) = as_variant(call)?;
+    let (name, fields) = as_variant(single_value(pallet_call)?)?;
+    if pallet != "Proxy" || !PROXY_CALLS.contains(&name) {
+        return None;
+    }
+
+    let force_proxy_type = match as_variant(field(fields, "force_proxy_type")?)? {
+        ("None", _) => None,
+        ("Some", proxy_type) => Some(as_variant(single_value(proxy_type)?)?.0.to_string()),
+        _ => return None,
+    };
+
+    Some(ProxyCall {
+        real: as_multi_address_id(field(fields, "real")?)?,
+        force_proxy_type,
+        call: field(fields, "call")?,
+    })
+}
+
+/// Decode the definitions in a `Proxy::Proxies` storage value,
+/// `(BoundedVec<ProxyDefinition>, Balance)`.
+pub fn proxy_definitions(proxies: &Value<u32>) -> Option<Vec<ProxyDefinition>> {
+    let ValueDef::Composite(proxies) = &proxies.value else {
+        return None;
+    };
+    let mut definitions = proxies.values().next()?;
+    // BoundedVec decodes as a newtype around the sequence of definitions.
+    if let ValueDef::Composite(composite) = &definitions.value {
+        if let Some(inner) = single_value(composite) {
+            if matches!(inner.value, ValueDef::Composite(Composite::Unnamed(_))) {
+                definitions = inner;
+            }
+        }
+    }
+    let ValueDef::Composite(definitions) = &definitions.value else {
+        return None;
+    };
+
+    definitions
+        .values()
+        .map(|definition| {
+            let ValueDef::Composite(fields) = &definition.value else {
+                return None;
+            };
+            Some(ProxyDefinition {
+                delegate: as_account_id(field(fields, "delegate")?)?,
+                proxy_type: as_variant(field(fields, "proxy_type")?)?.0.to_string(),
+                delay: as_u128(field(fields, "delay")?)?,
+            })
+        })
+        .collect()
+}
+
+/// Find a supported native or pallet-assets transfer in a call.
+pub fn find_transfer(call: &Value<u32>) -> Option<Transfer> {
+    let (pallet, pallet_call) = as_variant(call)?;
+    let (name, fields) = as_variant(single_value(pallet_call)?)?;
+
+    match pallet {
+        "Balances" if BALANCES_TRANSFER_CALLS.contains(&name) => Some(Transfer {
+            asset: PaymentAsset::Native,
+            dest: as_multi_address_id(field(fields, "dest")?)?,
+            amount: as_u128(field(fields, "value")?)?,
+        }),
+        "Assets" if ASSETS_TRANSFER_CALLS.contains(&name) => Some(Transfer {
+            asset: PaymentAsset::Asset {
+                id: u32::try_from(as_u128(field(fields, "id")?)?).ok()?,
+            },
+            dest: as_multi_address_id(field(fields, "target")?)?,
+            amount: as_u128(field(fields, "amount")?)?,
+        }),
+        "ForeignAssets" if ASSETS_TRANSFER_CALLS.contains(&name) => Some(Transfer {
+            asset: PaymentAsset::ForeignAsset {
+                location: field(fields, "id")?.clone().remove_context().to_string(),
+            },
+            dest: as_multi_address_id(field(fields, "target")?)?,
+            amount: as_u128(field(fields, "amount")?)?,
+        }),
+        _ => None,
+    }
+}
+
+pub(crate) fn as_variant(value: &Value<u32>) -> Option<(&str, &Composite<u32>)> {
+    match &value.value {
+        ValueDef::Variant(variant) => Some((variant.name.as_str(), &variant.values)),
+        _ => None,
+    }
+}
+
+pub(crate) fn single_value(composite: &Composite<u32>) -> Option<&Value<u32>> {
+    match composite {
+        Composite::Unnamed(values) if values.len() == 1 => values.first(),
+        Composite::Named(values) if values.len() == 1 => values.first().map(|(_, v)| v),
+        _ => None,
+    }
+}
+
+pub(crate) fn field<'a>(composite: &'a Composite<u32>, name: &str) -> Option<&'a Value<u32>> {
+    match composite {
+        Composite::Named(values) => values.iter().find(|(n, _)| n == name).map(|(_, v)| v),
+        Composite::Unnamed(_) => None,
+    }
+}
+
+pub(crate) fn as_u128(value: &Value<u32>) -> Option<u128> {
+    match &value.value {
+        ValueDef::Primitive(Primitive::U128(n)) => Some(*n),
+        // Compact-wrapped or newtype values decode as single-field composites.
+        ValueDef::Composite(composite) => as_u128(single_value(composite)?),
+        _ => None,
+    }
+}
+
+/// Collect a fixed-size byte array that may be nested in newtype composites,
+/// e.g. `AccountId32([u8; 32])`.
+pub(crate) fn as_bytes(value: &Value<u32>) -> Option<Vec<u8>> {
+    match &value.value {
+        ValueDef::Primitive(Primitive::U128(n)) => u8::try_from(*n).ok().map(|b| vec![b]),
+        ValueDef::Composite(composite) => {
+            let mut bytes = Vec::new();
+            for v in composite.values() {
+                bytes.extend(as_bytes(v)?);
+            }
+            Some(bytes)
+        }
+        _ => None,
+    }
+}
+
+pub(crate) fn as_account_id(value: &Value<u32>) -> Option<AccountId32> {
+    let bytes: [u8; 32] = as_bytes(value)?.try_into().ok()?;
+    Some(AccountId32::from(bytes))
+}
+
+pub(crate) fn as_multi_address_id(value: &Value<u32>) -> Option<AccountId32> {
+    match as_variant(value) {
+        Some(("Id", fields)) => as_account_id(single_value(fields)?),
+        Some(_) => None,
+        // Chains without MultiAddress use AccountId directly.
+        None => as_account_id(value),
+    }
+}
+
+fn invalid(message: String) -> FacilitatorError {
+    FacilitatorError::InvalidTransaction(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_era_decode_immortal() {
+        assert_eq!(Era::decode(&[0]), Some(Era::Immortal));
+    }
+
+    #[test]
+    fn test_era_decode_mortal() {
+        // period 64, phase 42 as encoded by polkadot.js
+        let era = Era::decode(&[0xa5, 0x02]).unwrap();
+        assert_eq!(era, Era::Mortal { period: 64, phase: 42 });
+        assert_eq!(era.birth(100), 42);
+        assert_eq!(era.birth(106), 106);
+        assert_eq!(era.last_block(100), Some(105));
+        assert_eq!(Era::Immortal.last_block(100), None);
+    }
+
+    #[test]
+    fn test_era_decode_invalid() {
+        assert!(Era::decode(&[]).is_none());
+        assert!(Era::decode(&[1, 2, 3]).is_none());
+    }
+
+    fn account_value(bytes: [u8; 32]) -> Value<()> {
+        Value::unnamed_composite(vec![Value::unnamed_composite(
+            bytes.iter().map(|b| Value::u128(*b as u128)),
+        )])
+    }
+
+    fn transfer_call(pallet: &str, call: &str, dest: [u8; 32], value: u128) -> Value<u32> {
+        Value::unnamed_variant(
+            pallet,
+            vec![Value::named_variant(
+                call,
+                vec![
+                    ("dest".to_string(), Value::unnamed_variant("Id", vec![account_value(dest)])),
+                    ("value".to_string(), Value::u128(value)),
+                ],
+            )],
+        )
+        .map_context(|_| 0u32)
+    }
+
+    fn asset_transfer_call(call: &str, id: u32, target: [u8; 32], amount: u128) -> Value<u32> {
+        pallet_asset_transfer_call(
+            "Assets",
+            call,
+            Value::unnamed_composite(vec![Value::u128(id as u128)]),
+            target,
+            amount,
+        )
+    }
+
+    fn pallet_asset_transfer_call(
+        pallet: &str,
+        call: &str,
+        id: Value<()>,
+        target: [u8; 32],
+        amount: u128,
+    ) -> Value<u32> {
+        Value::unnamed_variant(
+            pallet,
+            vec![Value::named_variant(
+                call,
+                vec![
+                    ("id".to_string(), id),
+                    ("target".to_string(), Value::unnamed_variant("Id", vec![account_value(target)])),
+                    ("amount".to_string(), Value::u128(amount)),
+                ],
+            )],
+        )
+        .map_context(|_| 0u32)
+    }
+
+    #[test]
+    fn test_find_balances_transfer() {
+        let call = transfer_call("Balances", "transfer_keep_alive", [7u8; 32], 1_000);
+        let transfer = find_transfer(&call).unwrap();
+        assert_eq!(transfer.asset, PaymentAsset::Native);
+        assert_eq!(transfer.dest, AccountId32::from([7u8; 32]));
+        assert_eq!(transfer.amount, 1_000);
+    }
+
+    #[test]
+    fn test_find_balances_transfer_allow_death() {
+        let call = transfer_call("Balances", "transfer_allow_death", [1u8; 32], 5);
+        assert!(find_transfer(&call).is_some());
+    }
+
+    #[test]
+    fn test_find_assets_transfer() {
+        let call = asset_transfer_call("transfer_keep_alive", 1984, [3u8; 32], 2_500_000);
+        let transfer = find_transfer(&call).unwrap();
+        assert_eq!(transfer.asset, PaymentAsset::Asset { id: 1984 });
+        assert_eq!(transfer.dest, AccountId32::from([3u8; 32]));
+        assert_eq!(transfer.amount, 2_500_000);
+
+        let call = asset_transfer_call("transfer", 1337, [3u8; 32], 1);
+        assert_eq!(find_transfer(&call).unwrap().asset, PaymentAsset::Asset { id: 1337 });
+    }
+
+    #[test]
+    fn test_find_foreign_assets_transfer() {
+        let location = Value::named_composite(vec![
+            ("parents", Value::u128(1)),
+            (
+                "interior",
+                Value::unnamed_variant(
+                    "X1",
+                    vec![Value::unnamed_composite(vec![Value::unnamed_variant(
+                        "Parachain",
+                        vec![Value::u128(2011)],
+                    )])],
+                ),
+            ),
+        ]);
+        let call = pallet_asset_transfer_call("ForeignAssets", "transfer", location.clone(), [4u8; 32], 10);
+        let transfer = find_transfer(&call).unwrap();
+        assert_eq!(
+            transfer.asset,
+            PaymentAsset::ForeignAsset {
+                location: location.to_string()
+            }
+        );
+        assert_eq!(transfer.amount, 10);
+    }
+
+    fn remark_call(remark: &[u8]) -> Value<()> {
+        Value::unnamed_variant(
+            "System",
+            vec![Value::named_variant(
+                "remark_with_event",
+                vec![("remark", Value::from_bytes(remark))],
+            )],
+        )
+    }
+
+    fn batch_call(name: &str, calls: Vec<Value<()>>) -> Value<u32> {
+        Value::unnamed_variant(
+            "Utility",
+            vec![Value::named_variant(name, vec![("calls", Value::unnamed_composite(calls))])],
+        )
+        .map_context(|_| 0u32)
+    }
+
+    #[test]
+    fn test_collect_payment_calls_in_batch() {
+        let transfer = transfer_call("Balances", "transfer_keep_alive", [9u8; 32], 42).remove_context();
+        let call = batch_call("batch_all", vec![transfer, remark_call(b"challenge-1")]);
+
+        let mut transfers = Vec::new();
+        let mut remarks = Vec::new();
+        collect_payment_calls(&call, &mut transfers, &mut remarks);
+
+        assert_eq!(transfers.len(), 1);
+        assert_eq!(transfers[0].amount, 42);
+        assert_eq!(remarks, vec![b"challenge-1".to_vec()]);
+    }
+
+    #[test]
+    fn test_collect_payment_calls_ignores_other_pallets() {
+        let transfer = transfer_call("Balances", "transfer_keep_alive", [9u8; 32], 42).remove_context();
+        let call = Value::unnamed_variant(
+            "Multisig",
+            vec![Value::named_variant("as_multi", vec![("calls", Value::unnamed_composite(vec![transfer]))])],
+        )
+        .map_context(|_| 0u32);
+
+        let mut transfers = Vec::new();
+        let mut remarks = Vec::new();
+        collect_payment_calls(&call, &mut transfers, &mut remarks);
+        assert!(transfers.is_empty());
+    }
+
+    fn proxy_call(real: [u8; 32], force_proxy_type: Option<&str>, call: Value<()>) -> Value<u32> {
+        let force_proxy_type = match force_proxy_type {
+            Some(proxy_type) => Value::unnamed_variant("Some", vec![Value::unnamed_variant(proxy_type, vec![])]),
+            None => Value::unnamed_variant("None", vec![]),
+        };
+        Value::unnamed_variant(
+            "Proxy",
+            vec![Value::named_variant(
+                "proxy",
+                vec![
+                    ("real", Value::unnamed_variant("Id", vec![account_value(real)])),
+                    ("force_proxy_type", force_proxy_type),
+                    ("call", call),
+                ],
+            )],
+        )
+        .map_context(|_| 0u32)
+    }
+
+    #[test]
+    fn test_find_proxy_call() {
+        let transfer = transfer_call("Balances", "transfer_keep_alive", [9u8; 32], 42);
+        let call = proxy_call([5u8; 32], Some("Any"), transfer.clone().remove_context());
+
+        let proxy = find_proxy_call(&call).unwrap();
+        assert_eq!(proxy.real, AccountId32::from([5u8; 32]));
+        assert_eq!(proxy.force_proxy_type.as_deref(), Some("Any"));
+        assert_eq!(find_transfer(proxy.call), find_transfer(&transfer));
+
+        let call = proxy_call([5u8; 32], None, transfer.clone().remove_context());
+        assert_eq!(find_proxy_call(&call).unwrap().force_proxy_type, None);
+
+        assert!(find_proxy_call(&transfer).is_none());
+    }
+
+    mod runtime {
+        //! Mirrors of the Asset Hub types behind `Proxy::Proxies`, for their
+        //! type information.
+        use scale_info::TypeInfo;
+
+        #[derive(TypeInfo)]
+        pub struct AccountId32(pub [u8; 32]);
+
+        #[derive(TypeInfo)]
+        pub enum ProxyType {
+            Any,
+            NonTransfer,
+            CancelProxy,
+            Assets,
+            AssetOwner,
+            AssetManager,
+            Collator,
+        }
+
+        #[derive(TypeInfo)]
+        pub struct ProxyDefinition {
+            pub delegate: AccountId32,
+            pub proxy_type: ProxyType,
+            pub delay: u32,
+        }
+
+        #[derive(TypeInfo)]
+        pub struct BoundedVec<T>(pub Vec<T>);
+
+        pub type Proxies = (BoundedVec<ProxyDefinition>, u128);
+    }
+
+    fn decode_proxies(bytes: &[u8]) -> Value<u32> {
+        let mut registry = scale_info::Registry::new();
+        let type_id = registry
+            .register_type(&scale_info::MetaType::new::<runtime::Proxies>())
+            .id;
+        let types: scale_info::PortableRegistry = registry.into();
+        scale_value::scale::decode_as_type(&mut &bytes[..], type_id, &types).unwrap()
+    }
+
+    #[test]
+    fn test_proxy_definitions() {
+        // Two definitions, as stored on chain: an `Any` proxy without delay
+        // and an `Assets` proxy with a 10 block delay, then the deposit.
+        let mut bytes = vec![0x08];
+        bytes.extend([1u8; 32]);
+        bytes.push(0);
+        bytes.extend(0u32.to_le_bytes());
+        bytes.extend([2u8; 32]);
+        bytes.push(3);
+        bytes.extend(10u32.to_le_bytes());
+        bytes.extend(200_000_000_000u128.to_le_bytes());
+
+        let definitions = proxy_definitions(&decode_proxies(&bytes)).unwrap();
+        assert_eq!(
+            definitions,
+            [
+                ProxyDefinition {
+                    delegate: AccountId32::from([1u8; 32]),
+                    proxy_type: "Any".to_string(),
+                    delay: 0,
+                },
+                ProxyDefinition {
+                    delegate: AccountId32::from([2u8; 32]),
+                    proxy_type: "Assets".to_string(),
+                    delay: 10,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_proxy_definitions_single_and_empty() {
+        let mut bytes = vec![0x04];
+        bytes.extend([1u8; 32]);
+        bytes.push(0);
+        bytes.extend(0u32.to_le_bytes());
+        bytes.extend(0u128.to_le_bytes());
+        assert_eq!(proxy_definitions(&decode_proxies(&bytes)).unwrap().len(), 1);
+
+        let mut bytes = vec![0x00];
+        bytes.extend(0u128.to_le_bytes());
+        assert_eq!(proxy_definitions(&decode_proxies(&bytes)), Some(Vec::new()));
+    }
+
+    #[test]
+    fn test_find_transfer_rejects_other_calls() {
+        let call = transfer_call("Balances", "force_transfer", [1u8; 32], 5);
+        assert!(find_transfer(&call).is_none());
+
+        let call = asset_transfer_call("transfer_approved", 1984, [1u8; 32], 5);
+        assert!(find_transfer(&call).is_none());
+    }
+}
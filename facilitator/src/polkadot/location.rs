@@ -0,0 +1,71 @@
+use crate::error::{FacilitatorError, FacilitatorResult};
+use subxt::ext::scale_value::{self, Value};
+use subxt::Metadata;
+
+/// Parse an XCM location written in scale-value syntax, e.g.
+/// `{ parents: 2, interior: X1((GlobalConsensus(Ethereum { chain_id: 1 }))) }`.
+pub fn parse_location(location: &str) -> FacilitatorResult<Value<()>> {
+    let (value, rest) = scale_value::stringify::from_str(location);
+    let value = value.map_err(|e| invalid_location(location, &e.to_string()))?;
+    if !rest.trim().is_empty() {
+        return Err(invalid_location(location, "unexpected trailing input"));
+    }
+    Ok(value)
+}
+
+/// Canonical string form of a location: parsed, checked against the chain's
+/// `ForeignAssets` asset ID type and printed back. Two locations are the same
+/// asset exactly when their canonical forms are equal.
+pub fn canonical_location(location: &str, metadata: &Metadata) -> FacilitatorResult<String> {
+    let type_id = foreign_asset_id_type(metadata)?;
+    let value = parse_location(location)?;
+
+    let mut encoded = Vec::new();
+    scale_value::scale::encode_as_type(&value, type_id, metadata.types(), &mut encoded)
+        .map_err(|e| invalid_location(location, &e.to_string()))?;
+
+    let decoded = scale_value::scale::decode_as_type(&mut &encoded[..], type_id, metadata.types())
+        .map_err(|e| invalid_location(location, &e.to_string()))?;
+    Ok(decoded.remove_context().to_string())
+}
+
+/// Type of the `id` argument of `ForeignAssets::transfer`.
+fn foreign_asset_id_type(metadata: &Metadata) -> FacilitatorResult<u32> {
+    metadata
+        .pallet_by_name("ForeignAssets")
+        .and_then(|pallet| pallet.call_variant_by_name("transfer"))
+        .and_then(|call| call.fields.iter().find(|f| f.name.as_deref() == Some("id")))
+        .map(|f| f.ty.id)
+        .ok_or_else(|| {
+            FacilitatorError::VerificationFailed(
+                "This network does not support foreign asset payments".to_string(),
+            )
+        })
+}
+
+fn invalid_location(location: &str, reason: &str) -> FacilitatorError {
+    FacilitatorError::VerificationFailed(format!(
+        "Invalid asset location {:?}: {}",
+        location, reason
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_location() {
+        let value = parse_location("{ parents: 1, interior: X1((Parachain(2011))) }").unwrap();
+        assert_eq!(
+            value.to_string(),
+            parse_location("{parents:1,interior:X1((Parachain(2011)))}").unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn test_parse_location_invalid() {
+        assert!(parse_location("{ parents: 1,").is_err());
+        assert!(parse_location("Here extra").is_err());
+    }
+}
@@ -1,9 +1,16 @@
 pub mod client;
+pub mod dry_run;
+pub mod events;
+pub mod extrinsic;
+pub mod location;
 pub mod networks;
+pub mod signature;
 pub mod types;
 pub mod validator;
+pub mod verified;
 
-pub use client::PolkadotClient;
+pub use client::{PendingSettlement, PolkadotClient, SignedTransaction};
+pub use extrinsic::DecodedExtrinsic;
 pub use networks::{find_healthy_node, NetworkConfig, RpcNode};
 pub use types::*;
 pub use validator::TransactionValidator;
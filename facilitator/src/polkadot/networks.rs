@@ -11,7 +11,11 @@ pub struct RpcNode {
 
 #[derive(Debug, Clone)]
 pub struct NetworkConfig {
+    pub id: String,
     pub name: String,
+    pub genesis_hash: String,
+    /// Extrinsic page URL with `{tx_hash}` and `{extrinsic_id}` placeholders.
+    pub explorer_url_template: String,
     pub nodes: Vec<RpcNode>,
     pub default_index: usize,
 }
@@ -19,7 +23,10 @@ pub struct NetworkConfig {
 impl NetworkConfig {
     pub fn paseo() -> Self {
         Self {
+            id: "paseo".to_string(),
             name: "Paseo Testnet".to_string(),
+            genesis_hash: "0x77afd6190f1554ad45fd0d31aee62aacc33c6db0ea801129acb813f913e0764f".to_string(),
+            explorer_url_template: "https://paseo.subscan.io/extrinsic/{tx_hash}".to_string(),
             nodes: vec![
                 RpcNode { url: "wss://rpc.ibp.network/paseo".to_string(), name: "IBP Network".to_string() },
                 RpcNode { url: "wss://paseo.rpc.amforc.com".to_string(), name: "Amforc".to_string() },
@@ -32,7 +39,10 @@ impl NetworkConfig {
 
     pub fn westend() -> Self {
         Self {
+            id: "westend".to_string(),
             name: "Westend Testnet".to_string(),
+            genesis_hash: "0xe143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e".to_string(),
+            explorer_url_template: "https://westend.subscan.io/extrinsic/{tx_hash}".to_string(),
             nodes: vec![
                 RpcNode { url: "wss://westend-rpc.polkadot.io".to_string(), name: "Parity".to_string() },
                 RpcNode { url: "wss://westend.rpc.amforc.com".to_string(), name: "Amforc".to_string() },
@@ -43,7 +53,10 @@ impl NetworkConfig {
 
     pub fn polkadot() -> Self {
         Self {
+            id: "polkadot".to_string(),
             name: "Polkadot Mainnet".to_string(),
+            genesis_hash: "0x91b171bb158e2d3848fa23a9f1c25182fb8e20313b2c1eb49219da7a70ce90c3".to_string(),
+            explorer_url_template: "https://polkadot.subscan.io/extrinsic/{tx_hash}".to_string(),
             nodes: vec![
                 RpcNode { url: "wss://rpc.polkadot.io".to_string(), name: "Parity".to_string() },
                 RpcNode { url: "wss://polkadot.rpc.amforc.com".to_string(), name: "Amforc".to_string() },
@@ -53,14 +66,68 @@ impl NetworkConfig {
         }
     }
 
+    pub fn asset_hub_paseo() -> Self {
+        Self {
+            id: "asset-hub-paseo".to_string(),
+            name: "Paseo Asset Hub".to_string(),
+            genesis_hash: "0xd6eec26135305a8ad257a20d003357284c8aa03d0bdb2b357ab0a22371e11ef2".to_string(),
+            explorer_url_template: "https://assethub-paseo.subscan.io/extrinsic/{tx_hash}".to_string(),
+            nodes: vec![
+                RpcNode { url: "wss://sys.ibp.network/asset-hub-paseo".to_string(), name: "IBP Network".to_string() },
+                RpcNode { url: "wss://asset-hub-paseo.dotters.network".to_string(), name: "Dotters".to_string() },
+                RpcNode { url: "wss://asset-hub-paseo-rpc.dwellir.com".to_string(), name: "Dwellir".to_string() },
+            ],
+            default_index: 0,
+        }
+    }
+
+    pub fn asset_hub_polkadot() -> Self {
+        Self {
+            id: "asset-hub-polkadot".to_string(),
+            name: "Polkadot Asset Hub".to_string(),
+            genesis_hash: "0x68d56f15f85d3136970ec16946040bc1752654e906147f7e43e9d539d7c3de2f".to_string(),
+            explorer_url_template: "https://assethub-polkadot.subscan.io/extrinsic/{tx_hash}".to_string(),
+            nodes: vec![
+                RpcNode { url: "wss://polkadot-asset-hub-rpc.polkadot.io".to_string(), name: "Parity".to_string() },
+                RpcNode { url: "wss://asset-hub-polkadot.dotters.network".to_string(), name: "Dotters".to_string() },
+                RpcNode { url: "wss://asset-hub-polkadot-rpc.dwellir.com".to_string(), name: "Dwellir".to_string() },
+            ],
+            default_index: 0,
+        }
+    }
+
     pub fn from_network_name(name: &str) -> Self {
         match name.to_lowercase().as_str() {
             "paseo" => Self::paseo(),
             "westend" => Self::westend(),
             "polkadot" => Self::polkadot(),
+            "asset-hub-paseo" => Self::asset_hub_paseo(),
+            "asset-hub-polkadot" => Self::asset_hub_polkadot(),
             _ => Self::paseo(),
         }
     }
+
+    /// Point settlement links at a different explorer, e.g. for a private chain.
+    pub fn with_explorer_url_template(mut self, template: String) -> Self {
+        self.explorer_url_template = template;
+        self
+    }
+
+    /// Explorer link for an extrinsic; `None` when the template needs the
+    /// extrinsic ID and the extrinsic is not in a block yet.
+    pub fn explorer_url(&self, tx_hash: &str, extrinsic_id: Option<&str>) -> Option<String> {
+        let url = self.explorer_url_template.replace("{tx_hash}", tx_hash);
+        match extrinsic_id {
+            Some(extrinsic_id) => Some(url.replace("{extrinsic_id}", extrinsic_id)),
+            None if url.contains("{extrinsic_id}") => None,
+            None => Some(url),
+        }
+    }
+
+    /// Whether a client-supplied network identifier refers to this network.
+    pub fn matches(&self, network: &str) -> bool {
+        self.id.eq_ignore_ascii_case(network)
+    }
 }
 
 /// Check if an RPC node is healthy by attempting a WebSocket connection
@@ -120,6 +187,41 @@ pub async fn find_healthy_node(config: &NetworkConfig) -> Option<RpcNode> {
     None
 }
 
+/// Order in which to try nodes after `failed_url` stopped responding: the
+/// nodes following it in the list, wrapping around, with the failed node last
+/// in case it was only a transient outage.
+fn failover_order(config: &NetworkConfig, failed_url: &str) -> Vec<usize> {
+    let count = config.nodes.len();
+    let start = config.nodes.iter()
+        .position(|node| node.url == failed_url)
+        .map(|i| i + 1)
+        .unwrap_or(config.default_index);
+    (0..count).map(|offset| (start + offset) % count).collect()
+}
+
+/// Find the next healthy RPC node after `failed_url`. Checks all nodes in
+/// parallel and returns the first healthy one in failover order.
+pub async fn find_failover_node(config: &NetworkConfig, failed_url: &str) -> Option<RpcNode> {
+    let timeout_ms = 5000u64;
+
+    let health_checks: Vec<_> = config.nodes.iter()
+        .map(|node| check_node_health(&node.url, timeout_ms))
+        .collect();
+
+    let results = join_all(health_checks).await;
+
+    for i in failover_order(config, failed_url) {
+        if results[i] {
+            let node = &config.nodes[i];
+            info!("Failing over to RPC: {} ({})", node.name, node.url);
+            return Some(node.clone());
+        }
+    }
+
+    warn!("No healthy RPC nodes found for failover");
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -136,4 +238,43 @@ mod tests {
         let config = NetworkConfig::from_network_name("paseo");
         assert_eq!(config.name, "Paseo Testnet");
     }
+
+    #[test]
+    fn test_from_network_name_asset_hub() {
+        assert_eq!(NetworkConfig::from_network_name("asset-hub-polkadot").name, "Polkadot Asset Hub");
+        assert_eq!(NetworkConfig::from_network_name("asset-hub-paseo").name, "Paseo Asset Hub");
+    }
+
+    #[test]
+    fn test_network_matches() {
+        let config = NetworkConfig::westend();
+        assert!(config.matches("westend"));
+        assert!(config.matches("Westend"));
+        assert!(!config.matches("paseo"));
+    }
+
+    #[test]
+    fn test_explorer_url() {
+        let config = NetworkConfig::polkadot();
+        assert_eq!(
+            config.explorer_url("0xabc", Some("100-2")).as_deref(),
+            Some("https://polkadot.subscan.io/extrinsic/0xabc")
+        );
+        assert!(config.explorer_url("0xabc", None).is_some());
+
+        let config = config.with_explorer_url_template("https://explorer.local/tx/{extrinsic_id}".to_string());
+        assert_eq!(
+            config.explorer_url("0xabc", Some("100-2")).as_deref(),
+            Some("https://explorer.local/tx/100-2")
+        );
+        assert_eq!(config.explorer_url("0xabc", None), None);
+    }
+
+    #[test]
+    fn test_failover_order() {
+        let config = NetworkConfig::paseo();
+        assert_eq!(failover_order(&config, "wss://paseo.rpc.amforc.com"), [2, 3, 0, 1]);
+        assert_eq!(failover_order(&config, "wss://paseo-rpc.dwellir.com"), [0, 1, 2, 3]);
+        assert_eq!(failover_order(&config, "wss://unknown.example"), [0, 1, 2, 3]);
+    }
 }
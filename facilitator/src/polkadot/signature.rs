@@ -0,0 +1,151 @@
+use crate::error::{FacilitatorError, FacilitatorResult};
+use blake2::digest::consts::U32;
+use blake2::{Blake2b, Digest};
+use subxt::utils::{AccountId32, MultiSignature};
+
+/// Signing context used by Substrate for sr25519 signatures.
+const SR25519_SIGNING_CONTEXT: &[u8] = b"substrate";
+
+/// Signed payloads longer than this are hashed before signing.
+const MAX_UNHASHED_PAYLOAD_LEN: usize = 256;
+
+pub fn blake2_256(data: &[u8]) -> [u8; 32] {
+    Blake2b::<U32>::digest(data).into()
+}
+
+/// Verify a MultiSignature over an extrinsic's signed payload.
+///
+/// `payload` is the raw `call ++ extra ++ additional_signed` encoding; it is
+/// hashed here when the runtime would have hashed it before signing.
+pub fn verify_signature(
+    signature: &MultiSignature,
+    signer: &AccountId32,
+    payload: &[u8],
+) -> FacilitatorResult<()> {
+    let hashed;
+    let message = if payload.len() > MAX_UNHASHED_PAYLOAD_LEN {
+        hashed = blake2_256(payload);
+        &hashed[..]
+    } else {
+        payload
+    };
+
+    let valid = match signature {
+        MultiSignature::Sr25519(sig) => verify_sr25519(sig, signer, message),
+        MultiSignature::Ed25519(sig) => verify_ed25519(sig, signer, message),
+        MultiSignature::Ecdsa(sig) => verify_ecdsa(sig, signer, message),
+    };
+
+    if !valid {
+        return Err(FacilitatorError::VerificationFailed(format!(
+            "Invalid signature for signer {}",
+            signer
+        )));
+    }
+    Ok(())
+}
+
+fn verify_sr25519(sig: &[u8; 64], signer: &AccountId32, message: &[u8]) -> bool {
+    let Ok(public) = schnorrkel::PublicKey::from_bytes(signer.as_ref()) else {
+        return false;
+    };
+    let Ok(signature) = schnorrkel::Signature::from_bytes(sig) else {
+        return false;
+    };
+    public
+        .verify_simple(SR25519_SIGNING_CONTEXT, message, &signature)
+        .is_ok()
+}
+
+fn verify_ed25519(sig: &[u8; 64], signer: &AccountId32, message: &[u8]) -> bool {
+    use ed25519_dalek::Verifier;
+
+    let public_bytes: &[u8; 32] = signer.as_ref();
+    let Ok(public) = ed25519_dalek::VerifyingKey::from_bytes(public_bytes) else {
+        return false;
+    };
+    let signature = ed25519_dalek::Signature::from_bytes(sig);
+    public.verify(message, &signature).is_ok()
+}
+
+/// ECDSA accounts are the blake2_256 hash of the compressed public key, so the
+/// key is recovered from the signature and hashed for comparison.
+fn verify_ecdsa(sig: &[u8; 65], signer: &AccountId32, message: &[u8]) -> bool {
+    use secp256k1::ecdsa::{RecoverableSignature, RecoveryId};
+
+    let v = if sig[64] >= 27 { sig[64] - 27 } else { sig[64] };
+    let Ok(recovery_id) = RecoveryId::from_i32(v as i32) else {
+        return false;
+    };
+    let Ok(signature) = RecoverableSignature::from_compact(&sig[..64], recovery_id) else {
+        return false;
+    };
+    let Ok(digest) = secp256k1::Message::from_digest_slice(&blake2_256(message)) else {
+        return false;
+    };
+
+    match secp256k1::Secp256k1::verification_only().recover_ecdsa(&digest, &signature) {
+        Ok(public) => blake2_256(&public.serialize()) == *AsRef::<[u8; 32]>::as_ref(signer),
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sr25519_signature() {
+        let keypair = subxt_signer::sr25519::dev::alice();
+        let signer = AccountId32::from(keypair.public_key().0);
+        let payload = b"payment payload";
+        let signature = MultiSignature::Sr25519(keypair.sign(payload).0);
+
+        assert!(verify_signature(&signature, &signer, payload).is_ok());
+        assert!(verify_signature(&signature, &signer, b"tampered payload").is_err());
+    }
+
+    #[test]
+    fn test_sr25519_long_payload_is_hashed() {
+        let keypair = subxt_signer::sr25519::dev::bob();
+        let signer = AccountId32::from(keypair.public_key().0);
+        let payload = vec![7u8; 300];
+        let signature = MultiSignature::Sr25519(keypair.sign(&blake2_256(&payload)).0);
+
+        assert!(verify_signature(&signature, &signer, &payload).is_ok());
+    }
+
+    #[test]
+    fn test_ed25519_signature() {
+        use ed25519_dalek::Signer;
+
+        let key = ed25519_dalek::SigningKey::from_bytes(&[1u8; 32]);
+        let signer = AccountId32::from(key.verifying_key().to_bytes());
+        let payload = b"payment payload";
+        let signature = MultiSignature::Ed25519(key.sign(payload).to_bytes());
+
+        assert!(verify_signature(&signature, &signer, payload).is_ok());
+        assert!(verify_signature(&signature, &AccountId32::from([0u8; 32]), payload).is_err());
+    }
+
+    #[test]
+    fn test_ecdsa_signature() {
+        let secp = secp256k1::Secp256k1::new();
+        let secret = secp256k1::SecretKey::from_slice(&[2u8; 32]).unwrap();
+        let public = secp256k1::PublicKey::from_secret_key(&secp, &secret);
+        let signer = AccountId32::from(blake2_256(&public.serialize()));
+
+        let payload = b"payment payload";
+        let digest = secp256k1::Message::from_digest_slice(&blake2_256(payload)).unwrap();
+        let (recovery_id, compact) = secp
+            .sign_ecdsa_recoverable(&digest, &secret)
+            .serialize_compact();
+        let mut sig = [0u8; 65];
+        sig[..64].copy_from_slice(&compact);
+        sig[64] = recovery_id.to_i32() as u8;
+        let signature = MultiSignature::Ecdsa(sig);
+
+        assert!(verify_signature(&signature, &signer, payload).is_ok());
+        assert!(verify_signature(&signature, &signer, b"tampered payload").is_err());
+    }
+}
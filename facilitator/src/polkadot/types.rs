@@ -1,25 +1,242 @@
 use serde::{Deserialize, Serialize};
 
+/// What a payment is denominated in.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PaymentAsset {
+    /// The chain's native token, moved with `Balances` transfers.
+    #[default]
+    Native,
+    /// A `pallet-assets` asset on Asset Hub, e.g. USDT (1984) or USDC (1337).
+    Asset { id: u32 },
+    /// A `ForeignAssets` asset on Asset Hub, identified by its XCM location
+    /// in scale-value syntax, e.g. `{ parents: 1, interior: X1((Parachain(2011))) }`.
+    ForeignAsset { location: String },
+}
+
+impl std::fmt::Display for PaymentAsset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PaymentAsset::Native => write!(f, "native"),
+            PaymentAsset::Asset { id } => write!(f, "asset {}", id),
+            PaymentAsset::ForeignAsset { location } => write!(f, "foreign asset {}", location),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TransactionData {
+    /// The account the payment is drawn from; differs from `signer` when the
+    /// transfer is dispatched through `Proxy::proxy`.
     pub from: String,
+    pub signer: String,
+    /// Proxy type forced by a `Proxy::proxy` call, if any.
+    pub proxy_type: Option<String>,
     pub to: String,
+    pub asset: PaymentAsset,
     pub amount: u128,
+    /// Remarks batched alongside the transfer.
+    pub remarks: Vec<Vec<u8>>,
     pub signature: String,
     pub nonce: u64,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ValidationParams {
     pub expected_amount: u128,
     pub expected_recipient: String,
+    pub expected_asset: PaymentAsset,
+    /// When set, the transfer must be batched with a remark equal to this
+    /// payment nonce, tying it to a single 402 challenge.
+    pub expected_remark: Option<Vec<u8>>,
 }
 
 impl ValidationParams {
-    pub fn new(expected_amount: u128, expected_recipient: String) -> Self {
+    pub fn new(
+        expected_amount: u128,
+        expected_recipient: String,
+        expected_asset: PaymentAsset,
+        expected_remark: Option<Vec<u8>>,
+    ) -> Self {
         Self {
             expected_amount,
             expected_recipient,
+            expected_asset,
+            expected_remark,
+        }
+    }
+}
+
+/// Bounds on how far a payment's nonce and mortality may stray from the
+/// current chain state before it is rejected.
+#[derive(Debug, Clone)]
+pub struct TransactionLimits {
+    pub max_nonce_gap: u64,
+    pub min_era_blocks_remaining: u64,
+}
+
+impl Default for TransactionLimits {
+    fn default() -> Self {
+        Self {
+            max_nonce_gap: 16,
+            min_era_blocks_remaining: 10,
+        }
+    }
+}
+
+/// Chain state that a signer commits to through the additional signed data
+/// of its signed extensions.
+#[derive(Debug, Clone)]
+pub struct ChainContext {
+    pub genesis_hash: [u8; 32],
+    pub spec_version: u32,
+    pub transaction_version: u32,
+    /// Hash of the block the extrinsic's mortal era starts at, or the
+    /// genesis hash for immortal extrinsics.
+    pub checkpoint_hash: [u8; 32],
+}
+
+/// Why a dry run predicts that an extrinsic would fail on chain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "code", rename_all = "snake_case")]
+pub enum DryRunFailure {
+    InsufficientBalance,
+    ExistentialDeposit,
+    StaleNonce,
+    FutureNonce,
+    ExpiredEra,
+    BadProof,
+    InvalidTransaction { kind: String },
+    UnknownTransaction { kind: String },
+    DispatchError { pallet: Option<String>, error: String },
+}
+
+impl std::fmt::Display for DryRunFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DryRunFailure::InsufficientBalance => write!(f, "insufficient balance"),
+            DryRunFailure::ExistentialDeposit => {
+                write!(f, "transfer would violate the existential deposit")
+            }
+            DryRunFailure::StaleNonce => write!(f, "nonce already used"),
+            DryRunFailure::FutureNonce => write!(f, "nonce is in the future"),
+            DryRunFailure::ExpiredEra => write!(f, "mortal era has expired"),
+            DryRunFailure::BadProof => write!(f, "bad signature proof"),
+            DryRunFailure::InvalidTransaction { kind } => write!(f, "invalid transaction: {}", kind),
+            DryRunFailure::UnknownTransaction { kind } => write!(f, "unknown transaction validity: {}", kind),
+            DryRunFailure::DispatchError { pallet: Some(pallet), error } => {
+                write!(f, "dispatch error: {}::{}", pallet, error)
+            }
+            DryRunFailure::DispatchError { pallet: None, error } => {
+                write!(f, "dispatch error: {}", error)
+            }
+        }
+    }
+}
+
+/// How far a submitted extrinsic has progressed on chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SettlementStatus {
+    /// Accepted into the transaction pool.
+    Validated,
+    InBestBlock,
+    Finalized,
+    Failed,
+    /// Reported as settled before finality, then dropped or failed.
+    Reverted,
+    /// Not confirmed within the settlement timeout; still being followed.
+    Pending,
+    /// Following the settlement failed without proving its outcome; the
+    /// chain is still being checked.
+    Unknown,
+}
+
+/// Where a settled payment landed on chain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SettlementResult {
+    /// blake2_256 hash of the encoded extrinsic.
+    pub tx_hash: String,
+    /// Block position, absent for settlements confirmed at `pool` level.
+    pub block_hash: Option<String>,
+    pub block_number: Option<u64>,
+    pub extrinsic_index: Option<u32>,
+    /// `<block_number>-<extrinsic_index>`, as used by block explorers.
+    pub extrinsic_id: Option<String>,
+    pub network: String,
+    pub explorer_url: Option<String>,
+    pub status: SettlementStatus,
+}
+
+/// How far `/settle` waits before reporting a settlement.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfirmationLevel {
+    /// Accepted into the transaction pool.
+    Pool,
+    /// Included, and its events checked, in a best block that may still be
+    /// retracted.
+    BestBlock,
+    /// Included in a GRANDPA-finalized block.
+    #[default]
+    Finalized,
+}
+
+/// A status change reported while a submitted extrinsic is being settled.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum SettlementUpdate {
+    Validated,
+    #[serde(rename = "broadcast")]
+    Broadcasted,
+    InBestBlock { block_hash: String },
+    /// The best block containing the extrinsic was retracted.
+    #[serde(rename = "retracted")]
+    NoLongerInBestBlock,
+    /// Finalized and checked against the block's events.
+    Finalized { settlement: SettlementResult },
+}
+
+impl SettlementUpdate {
+    /// Name of the update, matching its serialized `status` tag.
+    pub fn name(&self) -> &'static str {
+        match self {
+            SettlementUpdate::Validated => "validated",
+            SettlementUpdate::Broadcasted => "broadcast",
+            SettlementUpdate::InBestBlock { .. } => "in_best_block",
+            SettlementUpdate::NoLongerInBestBlock => "retracted",
+            SettlementUpdate::Finalized { .. } => "finalized",
+        }
+    }
+}
+
+/// Why an extrinsic that made it into a finalized block did not settle the
+/// payment.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "code", rename_all = "snake_case")]
+pub enum SettlementFailure {
+    /// `System::ExtrinsicFailed` with the decoded dispatch error.
+    ExtrinsicFailed { pallet: Option<String>, error: String },
+    /// The extrinsic succeeded without emitting the expected transfer event,
+    /// e.g. a proxied or batched transfer that failed inside its wrapper.
+    TransferMissing,
+    /// The extrinsic or its outcome was not found in the reported block.
+    ExtrinsicNotFound,
+}
+
+impl std::fmt::Display for SettlementFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SettlementFailure::ExtrinsicFailed { pallet: Some(pallet), error } => {
+                write!(f, "extrinsic failed: {}::{}", pallet, error)
+            }
+            SettlementFailure::ExtrinsicFailed { pallet: None, error } => {
+                write!(f, "extrinsic failed: {}", error)
+            }
+            SettlementFailure::TransferMissing => {
+                write!(f, "extrinsic succeeded without the expected transfer event")
+            }
+            SettlementFailure::ExtrinsicNotFound => write!(f, "extrinsic not found in block"),
         }
     }
 }
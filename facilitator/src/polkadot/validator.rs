@@ -1,5 +1,9 @@
 use crate::error::{FacilitatorError, FacilitatorResult};
-use crate::polkadot::types::{TransactionData, ValidationParams};
+use crate::polkadot::extrinsic::Era;
+use crate::polkadot::signature::verify_signature;
+use crate::polkadot::types::{PaymentAsset, TransactionData, ValidationParams};
+use std::str::FromStr;
+use subxt::utils::{AccountId32, MultiSignature};
 use tracing::{debug, warn};
 
 pub struct TransactionValidator;
@@ -11,14 +15,31 @@ impl TransactionValidator {
     ) -> FacilitatorResult<()> {
         debug!("Validating transaction: {:?}", tx_data);
 
+        Self::validate_asset(&tx_data.asset, &params.expected_asset)?;
         Self::validate_amount(tx_data.amount, params.expected_amount)?;
         Self::validate_recipient(&tx_data.to, &params.expected_recipient)?;
-        Self::validate_signature(&tx_data.signature)?;
+        if let Some(expected_remark) = &params.expected_remark {
+            Self::validate_remark(&tx_data.remarks, expected_remark)?;
+        }
 
         debug!("Transaction validation successful");
         Ok(())
     }
 
+    fn validate_asset(actual: &PaymentAsset, expected: &PaymentAsset) -> FacilitatorResult<()> {
+        if actual != expected {
+            warn!(
+                "Asset validation failed: actual={}, expected={}",
+                actual, expected
+            );
+            return Err(FacilitatorError::VerificationFailed(format!(
+                "Invalid asset: expected {}, got {}",
+                expected, actual
+            )));
+        }
+        Ok(())
+    }
+
     fn validate_amount(actual: u128, expected: u128) -> FacilitatorResult<()> {
         if actual < expected {
             warn!(
@@ -33,8 +54,22 @@ impl TransactionValidator {
         Ok(())
     }
 
+    /// Compare recipients as AccountId32 bytes, so the same account encoded
+    /// with different SS58 prefixes still matches.
     fn validate_recipient(actual: &str, expected: &str) -> FacilitatorResult<()> {
-        if actual != expected {
+        let expected_account = AccountId32::from_str(expected).map_err(|e| {
+            warn!("Recipient validation failed: invalid expected address {}: {}", expected, e);
+            FacilitatorError::VerificationFailed(format!(
+                "Invalid expected recipient {}: {}",
+                expected, e
+            ))
+        })?;
+        let actual_account = AccountId32::from_str(actual).map_err(|e| {
+            warn!("Recipient validation failed: invalid recipient address {}: {}", actual, e);
+            FacilitatorError::InvalidTransaction(format!("Invalid recipient {}: {}", actual, e))
+        })?;
+
+        if actual_account != expected_account {
             warn!(
                 "Recipient validation failed: actual={}, expected={}",
                 actual, expected
@@ -47,21 +82,90 @@ impl TransactionValidator {
         Ok(())
     }
 
-    fn validate_signature(signature: &str) -> FacilitatorResult<()> {
-        if signature.is_empty() {
-            warn!("Signature validation failed: empty signature");
+    fn validate_remark(remarks: &[Vec<u8>], expected: &[u8]) -> FacilitatorResult<()> {
+        if !remarks.iter().any(|remark| remark == expected) {
+            warn!(
+                "Remark validation failed: expected {}, found {} remarks",
+                String::from_utf8_lossy(expected),
+                remarks.len()
+            );
+            return Err(FacilitatorError::VerificationFailed(format!(
+                "Missing payment remark {}",
+                String::from_utf8_lossy(expected)
+            )));
+        }
+        Ok(())
+    }
+
+    /// Reject nonces that were already used or sit too far past the
+    /// account's next index to be included soon.
+    pub fn validate_nonce(nonce: u64, next_index: u64, max_gap: u64) -> FacilitatorResult<()> {
+        if nonce < next_index {
+            warn!("Nonce validation failed: nonce={}, next_index={}", nonce, next_index);
+            return Err(FacilitatorError::VerificationFailed(format!(
+                "Stale nonce: expected at least {}, got {}",
+                next_index, nonce
+            )));
+        }
+        if nonce - next_index > max_gap {
+            warn!("Nonce validation failed: nonce={}, next_index={}", nonce, next_index);
+            return Err(FacilitatorError::VerificationFailed(format!(
+                "Nonce too far in the future: next index is {}, got {}",
+                next_index, nonce
+            )));
+        }
+        Ok(())
+    }
+
+    /// Require a mortal era that is still valid for at least `min_remaining`
+    /// blocks after `current_block`.
+    pub fn validate_mortality(era: Era, current_block: u64, min_remaining: u64) -> FacilitatorResult<()> {
+        let Era::Mortal { period, .. } = era else {
+            warn!("Mortality validation failed: immortal transaction");
             return Err(FacilitatorError::VerificationFailed(
-                "Missing transaction signature".to_string(),
+                "Immortal transactions are not accepted".to_string(),
             ));
+        };
+
+        let expires_at = era.birth(current_block) + period;
+        let remaining = expires_at.saturating_sub(current_block);
+        if remaining < min_remaining {
+            warn!(
+                "Mortality validation failed: expires at {}, current block {}",
+                expires_at, current_block
+            );
+            return Err(FacilitatorError::VerificationFailed(format!(
+                "Transaction expires in {} blocks, at least {} required",
+                remaining, min_remaining
+            )));
         }
         Ok(())
     }
+
+    /// Check the extrinsic signature against its rebuilt signed payload.
+    pub fn validate_signature(
+        signature: &MultiSignature,
+        signer: &AccountId32,
+        payload: &[u8],
+    ) -> FacilitatorResult<()> {
+        verify_signature(signature, signer, payload).inspect_err(|_| {
+            warn!("Signature validation failed for signer {}", signer);
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_validate_asset() {
+        let usdt = PaymentAsset::Asset { id: 1984 };
+        assert!(TransactionValidator::validate_asset(&usdt, &usdt).is_ok());
+        assert!(TransactionValidator::validate_asset(&PaymentAsset::Native, &usdt).is_err());
+        assert!(TransactionValidator::validate_asset(&PaymentAsset::Asset { id: 1337 }, &usdt).is_err());
+    }
+
     #[test]
     fn test_validate_amount_success() {
         assert!(TransactionValidator::validate_amount(100, 100).is_ok());
@@ -80,20 +184,65 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_validate_recipient_across_prefixes() {
+        // Alice with the generic, Polkadot and Kusama prefixes
+        assert!(
+            TransactionValidator::validate_recipient("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY", "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5").is_ok()
+        );
+        assert!(
+            TransactionValidator::validate_recipient("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY", "HNZata7iMYWmk5RvZRTiAsSDhV8366zq2YGb3tLH5Upf74F").is_ok()
+        );
+    }
+
     #[test]
     fn test_validate_recipient_failure() {
         assert!(
             TransactionValidator::validate_recipient("invalid", "valid").is_err()
         );
+        assert!(
+            TransactionValidator::validate_recipient("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY", "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty").is_err()
+        );
+    }
+
+    #[test]
+    fn test_validate_remark() {
+        let remarks = vec![b"other".to_vec(), b"challenge-1".to_vec()];
+        assert!(TransactionValidator::validate_remark(&remarks, b"challenge-1").is_ok());
+        assert!(TransactionValidator::validate_remark(&remarks, b"challenge-2").is_err());
+        assert!(TransactionValidator::validate_remark(&[], b"challenge-1").is_err());
+    }
+
+    #[test]
+    fn test_validate_nonce() {
+        assert!(TransactionValidator::validate_nonce(5, 5, 16).is_ok());
+        assert!(TransactionValidator::validate_nonce(21, 5, 16).is_ok());
+        assert!(TransactionValidator::validate_nonce(4, 5, 16).is_err());
+        assert!(TransactionValidator::validate_nonce(22, 5, 16).is_err());
+    }
+
+    #[test]
+    fn test_validate_mortality() {
+        let era = Era::Mortal { period: 64, phase: 42 };
+        // Valid from block 42 to 106
+        assert!(TransactionValidator::validate_mortality(era, 50, 10).is_ok());
+        assert!(TransactionValidator::validate_mortality(era, 100, 10).is_err());
+        assert!(TransactionValidator::validate_mortality(Era::Immortal, 50, 10).is_err());
     }
 
     #[test]
     fn test_validate_signature_success() {
-        assert!(TransactionValidator::validate_signature("0x123abc").is_ok());
+        let keypair = subxt_signer::sr25519::dev::alice();
+        let signer = AccountId32::from(keypair.public_key().0);
+        let signature = MultiSignature::Sr25519(keypair.sign(b"payload").0);
+        assert!(TransactionValidator::validate_signature(&signature, &signer, b"payload").is_ok());
     }
 
     #[test]
     fn test_validate_signature_failure() {
-        assert!(TransactionValidator::validate_signature("").is_err());
+        let keypair = subxt_signer::sr25519::dev::alice();
+        let signer = AccountId32::from(subxt_signer::sr25519::dev::bob().public_key().0);
+        let signature = MultiSignature::Sr25519(keypair.sign(b"payload").0);
+        assert!(TransactionValidator::validate_signature(&signature, &signer, b"payload").is_err());
     }
 }
@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::polkadot::extrinsic::DecodedExtrinsic;
+use crate::polkadot::types::ValidationParams;
+
+/// How long a verified transaction is remembered by default.
+pub const DEFAULT_VERIFIED_TTL: Duration = Duration::from_secs(30);
+
+struct VerifiedTransaction {
+    extrinsic: DecodedExtrinsic,
+    params: ValidationParams,
+    network: String,
+    verified_at: Instant,
+}
+
+/// Transactions that recently passed verification, keyed by extrinsic hash,
+/// so a `/settle` right after `/verify` need not decode the transaction or
+/// query the chain again. Entries are short-lived: balances, nonces and the
+/// runtime can all change once they expire.
+pub struct VerifiedTransactions {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, VerifiedTransaction>>,
+}
+
+impl VerifiedTransactions {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Remember a verified transaction, dropping any that have expired.
+    pub fn insert(&self, tx_hash: String, extrinsic: DecodedExtrinsic, params: ValidationParams, network: String) {
+        let now = Instant::now();
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|_, entry| now.duration_since(entry.verified_at) < self.ttl);
+        entries.insert(
+            tx_hash,
+            VerifiedTransaction {
+                extrinsic,
+                params,
+                network,
+                verified_at: now,
+            },
+        );
+    }
+
+    /// Whether the transaction recently passed verification against exactly
+    /// these requirements.
+    pub fn is_verified(&self, tx_hash: &str, params: &ValidationParams, network: &str) -> bool {
+        self.entries
+            .lock()
+            .unwrap()
+            .get(tx_hash)
+            .is_some_and(|entry| {
+                entry.verified_at.elapsed() < self.ttl && entry.params == *params && entry.network == network
+            })
+    }
+
+    /// The decoded form of a recently verified transaction.
+    pub fn extrinsic(&self, tx_hash: &str) -> Option<DecodedExtrinsic> {
+        self.entries
+            .lock()
+            .unwrap()
+            .get(tx_hash)
+            .filter(|entry| entry.verified_at.elapsed() < self.ttl)
+            .map(|entry| entry.extrinsic.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polkadot::types::PaymentAsset;
+    use subxt::ext::scale_value::Value;
+    use subxt::utils::{AccountId32, MultiSignature};
+
+    fn extrinsic() -> DecodedExtrinsic {
+        DecodedExtrinsic {
+            signer: AccountId32::from([1u8; 32]),
+            signature: MultiSignature::Sr25519([0u8; 64]),
+            signed_extensions: Vec::new(),
+            call: Value::unnamed_composite(vec![]).map_context(|_| 0u32),
+            call_bytes: Vec::new(),
+        }
+    }
+
+    fn params(amount: u128) -> ValidationParams {
+        ValidationParams::new(amount, "recipient".to_string(), PaymentAsset::Native, None)
+    }
+
+    #[test]
+    fn test_verified_transaction() {
+        let verified = VerifiedTransactions::new(Duration::from_secs(60));
+        verified.insert("0x01".to_string(), extrinsic(), params(10), "paseo".to_string());
+
+        assert!(verified.is_verified("0x01", &params(10), "paseo"));
+        assert!(!verified.is_verified("0x01", &params(11), "paseo"));
+        assert!(!verified.is_verified("0x01", &params(10), "westend"));
+        assert!(!verified.is_verified("0x02", &params(10), "paseo"));
+        assert!(verified.extrinsic("0x01").is_some());
+    }
+
+    #[test]
+    fn test_verified_expiry() {
+        let verified = VerifiedTransactions::new(Duration::ZERO);
+        verified.insert("0x01".to_string(), extrinsic(), params(10), "paseo".to_string());
+
+        assert!(!verified.is_verified("0x01", &params(10), "paseo"));
+        assert!(verified.extrinsic("0x01").is_none());
+    }
+}
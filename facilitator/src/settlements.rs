@@ -0,0 +1,433 @@
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+use crate::error::{FacilitatorError, FacilitatorResult};
+use crate::ledger::{Attempt, AttemptKind, Ledger, LedgerStatus, PaymentDetails};
+use crate::polkadot::types::{SettlementFailure, SettlementResult, SettlementStatus, SettlementUpdate};
+
+/// Progress of a settlement that is followed beyond the `/settle` response:
+/// asynchronous settlements and those released before finality.
+#[derive(Debug, Clone, Serialize)]
+pub struct SettlementRecord {
+    pub id: String,
+    pub tx_hash: String,
+    pub status: SettlementStatus,
+    /// Status at which the settlement was reported to the caller as settled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub released_at: Option<SettlementStatus>,
+    /// How many times a best block including the extrinsic was retracted.
+    pub retractions: u32,
+    /// Block the extrinsic is currently included in, best or finalized.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub settlement: Option<SettlementResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub settlement_failure: Option<SettlementFailure>,
+}
+
+impl SettlementRecord {
+    fn new(id: String, tx_hash: String) -> Self {
+        Self {
+            id,
+            tx_hash,
+            status: SettlementStatus::Validated,
+            released_at: None,
+            retractions: 0,
+            block_hash: None,
+            settlement: None,
+            error: None,
+            settlement_failure: None,
+        }
+    }
+
+    fn apply(&mut self, update: &SettlementUpdate) {
+        match update {
+            SettlementUpdate::Validated | SettlementUpdate::Broadcasted => {}
+            SettlementUpdate::InBestBlock { block_hash } => {
+                self.status = SettlementStatus::InBestBlock;
+                self.block_hash = Some(block_hash.clone());
+            }
+            SettlementUpdate::NoLongerInBestBlock => {
+                // Back in the pool until the extrinsic lands in another block.
+                self.status = SettlementStatus::Validated;
+                self.block_hash = None;
+                self.retractions += 1;
+            }
+            SettlementUpdate::Finalized { settlement } => {
+                self.status = SettlementStatus::Finalized;
+                self.block_hash = settlement.block_hash.clone();
+                self.settlement = Some(settlement.clone());
+            }
+        }
+    }
+
+    /// Still on its way to finality without having been reported as settled.
+    fn is_unresolved(&self) -> bool {
+        self.released_at.is_none()
+            && matches!(
+                self.status,
+                SettlementStatus::Validated
+                    | SettlementStatus::InBestBlock
+                    | SettlementStatus::Pending
+                    | SettlementStatus::Unknown
+            )
+    }
+
+    /// Finalized or failed for good; no longer followed.
+    fn is_terminal(&self) -> bool {
+        matches!(
+            self.status,
+            SettlementStatus::Finalized | SettlementStatus::Failed | SettlementStatus::Reverted
+        )
+    }
+
+    fn release(&mut self, settlement: &SettlementResult) {
+        self.released_at = Some(settlement.status);
+        self.settlement = Some(settlement.clone());
+    }
+
+    fn fail(&mut self, error: &FacilitatorError) {
+        self.status = if self.released_at.is_some() {
+            SettlementStatus::Reverted
+        } else {
+            SettlementStatus::Failed
+        };
+        self.error = Some(error.to_string());
+        if let FacilitatorError::SettlementFailed(failure) = error {
+            self.settlement_failure = Some(failure.clone());
+        }
+    }
+}
+
+#[derive(Default)]
+struct Records {
+    by_id: HashMap<String, SettlementRecord>,
+    /// Settlement ID of each transaction's unresolved settlement.
+    unresolved: HashMap<String, String>,
+    /// Terminal settlements in the order they resolved, for eviction.
+    resolved: VecDeque<(Instant, String)>,
+}
+
+impl Records {
+    /// Apply `change` to a record, then bring the indexes up to date with
+    /// its new state.
+    fn update<T>(&mut self, id: &str, change: impl FnOnce(&mut SettlementRecord) -> T) -> Option<T> {
+        let record = self.by_id.get_mut(id)?;
+        let was_terminal = record.is_terminal();
+        let changed = change(record);
+
+        if !record.is_unresolved() && self.unresolved.get(&record.tx_hash).map(String::as_str) == Some(id) {
+            self.unresolved.remove(&record.tx_hash);
+        }
+        if record.is_terminal() && !was_terminal {
+            self.resolved.push_back((Instant::now(), id.to_string()));
+        }
+        Some(changed)
+    }
+
+    /// Drop settlements that resolved more than `ttl` ago.
+    fn evict(&mut self, ttl: Duration) {
+        let now = Instant::now();
+        while let Some((resolved_at, _)) = self.resolved.front() {
+            if now.duration_since(*resolved_at) < ttl {
+                break;
+            }
+            if let Some((_, id)) = self.resolved.pop_front() {
+                self.by_id.remove(&id);
+            }
+        }
+    }
+}
+
+/// In-memory registry of followed settlements, keyed by settlement ID and
+/// kept for `ttl` once finalized or failed. Every status change is also
+/// recorded in the ledger under the same ID.
+pub struct SettlementTracker {
+    ttl: Duration,
+    records: Mutex<Records>,
+    ledger: Arc<Ledger>,
+}
+
+impl SettlementTracker {
+    pub fn new(ledger: Arc<Ledger>, ttl: Duration) -> Self {
+        Self {
+            ttl,
+            records: Mutex::new(Records::default()),
+            ledger,
+        }
+    }
+
+    /// Start tracking a transaction and return its settlement ID. Refused
+    /// while another settlement of the same transaction is still unresolved.
+    pub fn create(
+        &self,
+        tx_hash: String,
+        network: &str,
+        payment: Option<PaymentDetails>,
+    ) -> FacilitatorResult<String> {
+        let id = Uuid::new_v4().to_string();
+        {
+            let mut records = self.records.lock().unwrap();
+            records.evict(self.ttl);
+            if let Some(existing) = records.unresolved.get(&tx_hash) {
+                return Err(FacilitatorError::SettlementInProgress(existing.clone()));
+            }
+            records.unresolved.insert(tx_hash.clone(), id.clone());
+            records
+                .by_id
+                .insert(id.clone(), SettlementRecord::new(id.clone(), tx_hash.clone()));
+        }
+
+        let attempt = Attempt {
+            id: &id,
+            kind: AttemptKind::Settle,
+            tx_hash: &tx_hash,
+            network,
+            payment,
+        };
+        self.ledger.record(attempt, LedgerStatus::Submitted, None);
+        Ok(id)
+    }
+
+    /// The settlement already reported for a transaction, if any.
+    pub async fn settled(&self, tx_hash: &str) -> Option<SettlementResult> {
+        self.ledger.settled(tx_hash).await
+    }
+
+    pub fn get(&self, id: &str) -> Option<SettlementRecord> {
+        let mut records = self.records.lock().unwrap();
+        records.evict(self.ttl);
+        records.by_id.get(id).cloned()
+    }
+
+    pub fn apply_update(&self, id: &str, update: &SettlementUpdate) {
+        self.records.lock().unwrap().update(id, |record| record.apply(update));
+
+        let (status, detail) = match update {
+            SettlementUpdate::Validated => (LedgerStatus::Validated, None),
+            SettlementUpdate::Broadcasted => return,
+            SettlementUpdate::InBestBlock { block_hash } => (LedgerStatus::InBestBlock, Some(block_hash.clone())),
+            SettlementUpdate::NoLongerInBestBlock => {
+                (LedgerStatus::Validated, Some("Retracted from best block".to_string()))
+            }
+            SettlementUpdate::Finalized { settlement } => {
+                self.ledger.settle(id, settlement);
+                (LedgerStatus::Finalized, settlement.block_hash.clone())
+            }
+        };
+        self.ledger.transition(id, status, detail);
+    }
+
+    /// Record that the settlement was reported as settled before finality.
+    pub fn release(&self, id: &str, settlement: &SettlementResult) {
+        self.records.lock().unwrap().update(id, |record| record.release(settlement));
+        self.ledger.settle(id, settlement);
+    }
+
+    /// Record a failure, returning the resulting status: `Reverted` when the
+    /// settlement had already been reported as settled.
+    pub fn fail(&self, id: &str, error: &FacilitatorError) -> Option<SettlementStatus> {
+        let status = self.records.lock().unwrap().update(id, |record| {
+            record.fail(error);
+            record.status
+        })?;
+        self.ledger.transition(id, status.into(), Some(error.to_string()));
+        Some(status)
+    }
+
+    /// Record that following the settlement failed without proving its
+    /// outcome; the chain is still being checked.
+    pub fn lose_track(&self, id: &str, error: &FacilitatorError) {
+        self.records.lock().unwrap().update(id, |record| {
+            record.status = SettlementStatus::Unknown;
+            record.error = Some(error.to_string());
+        });
+        self.ledger.transition(id, LedgerStatus::Unknown, Some(error.to_string()));
+    }
+
+    /// Record that the settlement was not confirmed within the settlement
+    /// timeout and is still being followed.
+    pub fn time_out(&self, id: &str) {
+        self.records.lock().unwrap().update(id, |record| {
+            record.status = SettlementStatus::Pending;
+        });
+        self.ledger.transition(id, LedgerStatus::Pending, None);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tracker() -> SettlementTracker {
+        SettlementTracker::new(Arc::new(Ledger::in_memory()), Duration::from_secs(60))
+    }
+
+    fn settlement() -> SettlementResult {
+        SettlementResult {
+            tx_hash: "0x01".to_string(),
+            block_hash: Some("0x02".to_string()),
+            block_number: Some(7),
+            extrinsic_index: Some(1),
+            extrinsic_id: Some("7-1".to_string()),
+            network: "paseo".to_string(),
+            explorer_url: Some("https://paseo.subscan.io/extrinsic/0x01".to_string()),
+            status: SettlementStatus::Finalized,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_settlement_progress() {
+        let tracker = tracker();
+        let id = tracker.create("0x01".to_string(), "paseo", None).unwrap();
+        assert_eq!(tracker.get(&id).unwrap().status, SettlementStatus::Validated);
+
+        tracker.apply_update(&id, &SettlementUpdate::InBestBlock { block_hash: "0xaa".to_string() });
+        let record = tracker.get(&id).unwrap();
+        assert_eq!(record.status, SettlementStatus::InBestBlock);
+        assert_eq!(record.block_hash.as_deref(), Some("0xaa"));
+
+        tracker.apply_update(&id, &SettlementUpdate::NoLongerInBestBlock);
+        let record = tracker.get(&id).unwrap();
+        assert_eq!(record.status, SettlementStatus::Validated);
+        assert_eq!(record.block_hash, None);
+        assert_eq!(record.retractions, 1);
+
+        tracker.apply_update(&id, &SettlementUpdate::Finalized { settlement: settlement() });
+        let record = tracker.get(&id).unwrap();
+        assert_eq!(record.status, SettlementStatus::Finalized);
+        assert_eq!(record.block_hash.as_deref(), Some("0x02"));
+        assert_eq!(record.settlement, Some(settlement()));
+
+        let entry = tracker.ledger.get(&id).await.unwrap().unwrap();
+        let statuses: Vec<_> = entry.transitions.iter().map(|t| t.status).collect();
+        assert_eq!(
+            statuses,
+            [
+                LedgerStatus::Submitted,
+                LedgerStatus::InBestBlock,
+                LedgerStatus::Validated,
+                LedgerStatus::Finalized
+            ]
+        );
+    }
+
+    #[test]
+    fn test_settlement_failure() {
+        let tracker = tracker();
+        let id = tracker.create("0x01".to_string(), "paseo", None).unwrap();
+
+        let status = tracker.fail(&id, &FacilitatorError::SettlementFailed(SettlementFailure::TransferMissing));
+        assert_eq!(status, Some(SettlementStatus::Failed));
+        let record = tracker.get(&id).unwrap();
+        assert_eq!(record.settlement_failure, Some(SettlementFailure::TransferMissing));
+        assert!(record.error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_released_settlement_reverted() {
+        let tracker = tracker();
+        let id = tracker.create("0x01".to_string(), "paseo", None).unwrap();
+
+        let mut early = settlement();
+        early.status = SettlementStatus::InBestBlock;
+        tracker.release(&id, &early);
+        tracker.apply_update(&id, &SettlementUpdate::NoLongerInBestBlock);
+
+        let status = tracker.fail(&id, &FacilitatorError::TransactionDropped("usurped".to_string()));
+        assert_eq!(status, Some(SettlementStatus::Reverted));
+        assert_eq!(tracker.get(&id).unwrap().released_at, Some(SettlementStatus::InBestBlock));
+        assert_eq!(tracker.ledger.get(&id).await.unwrap().unwrap().status, LedgerStatus::Reverted);
+    }
+
+    #[tokio::test]
+    async fn test_timed_out_settlement() {
+        let tracker = tracker();
+        let id = tracker.create("0x01".to_string(), "paseo", None).unwrap();
+
+        tracker.time_out(&id);
+        assert_eq!(tracker.get(&id).unwrap().status, SettlementStatus::Pending);
+        assert!(tracker.create("0x01".to_string(), "paseo", None).is_err());
+
+        tracker.lose_track(&id, &FacilitatorError::PolkadotRpcError("No settlement status within 60s".to_string()));
+        assert_eq!(tracker.get(&id).unwrap().status, SettlementStatus::Unknown);
+
+        tracker.apply_update(&id, &SettlementUpdate::Finalized { settlement: settlement() });
+        assert_eq!(tracker.get(&id).unwrap().status, SettlementStatus::Finalized);
+        let entry = tracker.ledger.get(&id).await.unwrap().unwrap();
+        let statuses: Vec<_> = entry.transitions.iter().map(|t| t.status).collect();
+        assert_eq!(
+            statuses,
+            [
+                LedgerStatus::Submitted,
+                LedgerStatus::Pending,
+                LedgerStatus::Unknown,
+                LedgerStatus::Finalized
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_lost_settlement_unknown() {
+        let tracker = tracker();
+        let id = tracker.create("0x01".to_string(), "paseo", None).unwrap();
+
+        tracker.lose_track(&id, &FacilitatorError::PolkadotRpcError("connection reset".to_string()));
+        let record = tracker.get(&id).unwrap();
+        assert_eq!(record.status, SettlementStatus::Unknown);
+        assert!(record.error.is_some());
+        assert!(tracker.create("0x01".to_string(), "paseo", None).is_err());
+        assert_eq!(tracker.ledger.get(&id).await.unwrap().unwrap().status, LedgerStatus::Unknown);
+
+        tracker.apply_update(&id, &SettlementUpdate::Finalized { settlement: settlement() });
+        assert_eq!(tracker.get(&id).unwrap().status, SettlementStatus::Finalized);
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_settlement() {
+        let tracker = tracker();
+        let id = tracker.create("0x01".to_string(), "paseo", None).unwrap();
+        assert!(matches!(
+            tracker.create("0x01".to_string(), "paseo", None),
+            Err(FacilitatorError::SettlementInProgress(existing)) if existing == id
+        ));
+
+        tracker.apply_update(&id, &SettlementUpdate::Finalized { settlement: settlement() });
+        assert_eq!(tracker.settled("0x01").await, Some(settlement()));
+        assert_eq!(tracker.settled("0x02").await, None);
+    }
+
+    #[test]
+    fn test_resolved_settlements_evicted() {
+        let tracker = SettlementTracker::new(Arc::new(Ledger::in_memory()), Duration::ZERO);
+        let finalized = tracker.create("0x01".to_string(), "paseo", None).unwrap();
+        let followed = tracker.create("0x02".to_string(), "paseo", None).unwrap();
+
+        tracker.apply_update(&finalized, &SettlementUpdate::Finalized { settlement: settlement() });
+        tracker.time_out(&followed);
+        assert!(tracker.get(&finalized).is_none());
+        assert_eq!(tracker.get(&followed).unwrap().status, SettlementStatus::Pending);
+    }
+
+    #[test]
+    fn test_failed_settlement_retried() {
+        let tracker = tracker();
+        let id = tracker.create("0x01".to_string(), "paseo", None).unwrap();
+        tracker.fail(&id, &FacilitatorError::TransactionDropped("usurped".to_string()));
+
+        let retry = tracker.create("0x01".to_string(), "paseo", None).unwrap();
+        assert_ne!(retry, id);
+        assert_eq!(tracker.get(&id).unwrap().status, SettlementStatus::Failed);
+    }
+
+    #[test]
+    fn test_unknown_settlement() {
+        assert!(tracker().get("missing").is_none());
+    }
+}
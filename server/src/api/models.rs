@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::facilitator::SettlementResult;
+
 #[derive(Debug, Serialize)]
 pub struct HealthResponse {
     pub status: String,
@@ -17,5 +19,5 @@ pub struct FreeResponse {
 pub struct PaidResponse {
     pub message: String,
     pub data: String,
-    pub transaction_hash: String,
+    pub settlement: SettlementResult,
 }
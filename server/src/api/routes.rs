@@ -9,10 +9,13 @@ use tracing::{info, warn};
 
 use crate::{
     api::models::{FreeResponse, HealthResponse, PaidResponse},
-    config::Config,
-    error::ServerResult,
-    facilitator::FacilitatorClient,
-    x402::{create_payment_required_response, extract_payment_header, PaymentRequirements},
+    config::{Config, PaidRoute},
+    error::{ServerError, ServerResult},
+    facilitator::{ExpectedPayment, FacilitatorClient, SettlementResult},
+    x402::{
+        create_payment_required_response, extract_payment_header, GrantedPayments, PaymentNonces,
+        PaymentRequirements,
+    },
 };
 
 pub type AppState = Arc<AppStateInner>;
@@ -20,6 +23,8 @@ pub type AppState = Arc<AppStateInner>;
 pub struct AppStateInner {
     pub config: Config,
     pub facilitator_client: FacilitatorClient,
+    pub payment_nonces: PaymentNonces,
+    pub granted_payments: GrantedPayments,
 }
 
 pub async fn health(State(state): State<AppState>) -> Json<HealthResponse> {
@@ -51,20 +56,47 @@ pub async fn paid(
         Some(payment_header) => {
             info!("Payment header found, verifying payment");
 
-            match verify_and_settle_payment(&state, &payment_header.transaction).await {
-                Ok(tx_hash) => {
-                    info!("Payment successful - Transaction Hash: {}", tx_hash);
+            // Redeem the nonce before settling, so two payments bound to the
+            // same challenge cannot both be granted; it is restored if this
+            // payment does not go through.
+            let payment_nonce = if state.config.require_payment_remark {
+                let redeemed = payment_header
+                    .nonce
+                    .as_deref()
+                    .and_then(|nonce| Some((nonce, state.payment_nonces.consume(nonce)?)));
+                match redeemed {
+                    Some(redeemed) => Some(redeemed),
+                    None => {
+                        warn!("Payment rejected: missing or unknown payment nonce");
+                        return Err(ServerError::InvalidPaymentHeader(
+                            "Missing or unknown payment nonce".to_string(),
+                        )
+                        .into_response());
+                    }
+                }
+            } else {
+                None
+            };
+
+            let route = &state.config.paid_route;
+            let nonce = payment_nonce.map(|(nonce, _)| nonce);
+            match verify_and_settle_payment(&state, route, &payment_header.transaction, nonce).await {
+                Ok(settlement) => {
+                    info!("Payment successful - Transaction Hash: {}", settlement.tx_hash);
                     Ok((
                         StatusCode::OK,
                         Json(PaidResponse {
                             message: "Payment successful".to_string(),
                             data: "This is protected content that requires payment".to_string(),
-                            transaction_hash: tx_hash,
+                            settlement,
                         }),
                     ))
                 }
                 Err(e) => {
                     warn!("Payment verification/settlement failed: {}", e);
+                    if let Some((nonce, issued_at)) = payment_nonce {
+                        state.payment_nonces.restore(nonce, issued_at);
+                    }
                     Err(e.into_response())
                 }
             }
@@ -72,11 +104,16 @@ pub async fn paid(
         None => {
             info!("No payment header found, returning 402 Payment Required");
 
-            let payment_requirements = PaymentRequirements::new(
+            let mut payment_requirements = PaymentRequirements::new(
                 state.config.default_price,
                 state.config.receiver_wallet_address.clone(),
                 state.config.polkadot_network.clone(),
+                state.config.payment_asset.clone(),
+                state.config.payment_currency.clone(),
             );
+            if state.config.require_payment_remark {
+                payment_requirements = payment_requirements.with_nonce(state.payment_nonces.issue());
+            }
 
             Err(create_payment_required_response(payment_requirements))
         }
@@ -85,33 +122,91 @@ pub async fn paid(
 
 async fn verify_and_settle_payment(
     state: &AppState,
+    route: &PaidRoute,
     transaction: &str,
-) -> ServerResult<String> {
-    info!("Verifying payment");
-
-    let is_valid = state
+    payment_nonce: Option<&str>,
+) -> ServerResult<SettlementResult> {
+    info!("Verifying and settling payment");
+
+    let requirements = ExpectedPayment {
+        expected_amount: state.config.default_price,
+        expected_recipient: state.config.receiver_wallet_address.clone(),
+        expected_asset: state.config.payment_asset.clone(),
+        network: state.config.polkadot_network.clone(),
+        payment_nonce: payment_nonce.map(str::to_string),
+    };
+    let settled = state
         .facilitator_client
-        .verify_payment(
-            transaction,
-            state.config.default_price,
-            &state.config.receiver_wallet_address,
-        )
+        .settle_payment(transaction, requirements, route.confirmation)
         .await?;
+    let settlement = settled.settlement;
+    if settled.replayed {
+        info!("Payment {} was settled by an earlier request", settlement.tx_hash);
+    }
+    grant_payment(&state.granted_payments, route, settlement)
+}
 
-    if !is_valid {
-        warn!("Payment verification failed");
-        return Err(crate::error::ServerError::PaymentVerificationFailed(
-            "Invalid payment".to_string(),
-        ));
+/// Grant access for a settled payment unless it already unlocked a route,
+/// which only routes that allow re-use accept.
+fn grant_payment(
+    granted_payments: &GrantedPayments,
+    route: &PaidRoute,
+    settlement: SettlementResult,
+) -> ServerResult<SettlementResult> {
+    if granted_payments.grant(&settlement.tx_hash) {
+        info!(
+            "Payment settled successfully - TX Hash: {} ({:?})",
+            settlement.tx_hash, settlement.status
+        );
+        Ok(settlement)
+    } else if route.allow_payment_reuse {
+        info!("Re-using payment {}", settlement.tx_hash);
+        Ok(settlement)
+    } else {
+        warn!("Payment {} was already used", settlement.tx_hash);
+        Err(ServerError::PaymentAlreadyUsed(settlement.tx_hash))
     }
+}
 
-    info!("Payment verified, settling transaction");
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::facilitator::{ConfirmationLevel, SettlementStatus};
+    use std::time::Duration;
+
+    fn settlement(tx_hash: &str) -> SettlementResult {
+        SettlementResult {
+            tx_hash: tx_hash.to_string(),
+            block_hash: None,
+            block_number: None,
+            extrinsic_index: None,
+            extrinsic_id: None,
+            network: "paseo".to_string(),
+            explorer_url: None,
+            status: SettlementStatus::Finalized,
+        }
+    }
 
-    let tx_hash = state
-        .facilitator_client
-        .settle_payment(transaction)
-        .await?;
+    #[test]
+    fn test_payment_granted_once() {
+        let granted = GrantedPayments::new(Duration::from_secs(60));
+        let route = PaidRoute {
+            confirmation: ConfirmationLevel::Finalized,
+            allow_payment_reuse: false,
+        };
+
+        // A replayed settlement this server never granted, e.g. after a
+        // pending first attempt, still unlocks the route.
+        assert!(grant_payment(&granted, &route, settlement("0x01")).is_ok());
+        assert!(matches!(
+            grant_payment(&granted, &route, settlement("0x01")),
+            Err(ServerError::PaymentAlreadyUsed(_))
+        ));
 
-    info!("Payment settled successfully - TX Hash: {}", tx_hash);
-    Ok(tx_hash)
+        let reusable = PaidRoute {
+            confirmation: ConfirmationLevel::Finalized,
+            allow_payment_reuse: true,
+        };
+        assert!(grant_payment(&granted, &reusable, settlement("0x01")).is_ok());
+    }
 }
@@ -1,20 +1,67 @@
 use anyhow::{Context, Result};
 use std::env;
 
+use crate::facilitator::ConfirmationLevel;
+use crate::x402::{ss58::decode_ss58, PaymentAsset};
+
 #[derive(Debug, Clone)]
 pub struct Config {
     pub server_host: String,
     pub server_port: u16,
     pub facilitator_url: String,
+    /// Bound on each facilitator request; must exceed the facilitator's
+    /// `SETTLEMENT_TIMEOUT_SECS`.
+    pub facilitator_timeout_secs: u64,
     pub receiver_wallet_address: String,
     pub default_price: u128,
     pub polkadot_network: String,
+    pub payment_asset: PaymentAsset,
+    pub payment_currency: String,
+    pub require_payment_remark: bool,
+    pub payment_nonce_ttl_secs: u64,
+    /// How long a payment that granted access stays recorded as used.
+    pub granted_payment_ttl_secs: u64,
+    pub paid_route: PaidRoute,
+}
+
+/// Payment policy of a single paid route.
+#[derive(Debug, Clone)]
+pub struct PaidRoute {
+    /// Confirmation level the facilitator waits for before the route
+    /// releases its content.
+    pub confirmation: ConfirmationLevel,
+    /// Whether the route grants access again for a payment that already
+    /// unlocked a route.
+    pub allow_payment_reuse: bool,
 }
 
 impl Config {
     pub fn from_env() -> Result<Self> {
         dotenvy::dotenv().ok();
 
+        let receiver_wallet_address = env::var("RECEIVER_WALLET_ADDRESS")
+            .context("RECEIVER_WALLET_ADDRESS must be set")?;
+        decode_ss58(&receiver_wallet_address).with_context(|| {
+            format!(
+                "RECEIVER_WALLET_ADDRESS must be a valid SS58 address, got {:?}",
+                receiver_wallet_address
+            )
+        })?;
+
+        let payment_asset = match (env::var("PAYMENT_ASSET_ID"), env::var("PAYMENT_ASSET_LOCATION")) {
+            (Ok(_), Ok(_)) => {
+                anyhow::bail!("Set only one of PAYMENT_ASSET_ID and PAYMENT_ASSET_LOCATION")
+            }
+            (Ok(id), Err(_)) => PaymentAsset::Asset {
+                id: id.parse().context("PAYMENT_ASSET_ID must be a valid u32")?,
+            },
+            (Err(_), Ok(location)) => PaymentAsset::ForeignAsset { location },
+            (Err(_), Err(_)) => PaymentAsset::Native,
+        };
+
+        let polkadot_network = env::var("POLKADOT_NETWORK")
+            .unwrap_or_else(|_| "westend".to_string());
+
         Ok(Self {
             server_host: env::var("SERVER_HOST")
                 .unwrap_or_else(|_| "127.0.0.1".to_string()),
@@ -24,14 +71,41 @@ impl Config {
                 .context("SERVER_PORT must be a valid u16")?,
             facilitator_url: env::var("FACILITATOR_URL")
                 .context("FACILITATOR_URL must be set")?,
-            receiver_wallet_address: env::var("RECEIVER_WALLET_ADDRESS")
-                .context("RECEIVER_WALLET_ADDRESS must be set")?,
+            facilitator_timeout_secs: env::var("FACILITATOR_TIMEOUT_SECS")
+                .unwrap_or_else(|_| "90".to_string())
+                .parse()
+                .context("FACILITATOR_TIMEOUT_SECS must be a valid u64")?,
+            receiver_wallet_address,
             default_price: env::var("DEFAULT_PRICE")
                 .unwrap_or_else(|_| "1000000000000".to_string())
                 .parse()
                 .context("DEFAULT_PRICE must be a valid u128")?,
-            polkadot_network: env::var("POLKADOT_NETWORK")
-                .unwrap_or_else(|_| "westend".to_string()),
+            payment_asset,
+            payment_currency: env::var("PAYMENT_CURRENCY")
+                .unwrap_or_else(|_| native_currency(&polkadot_network).to_string()),
+            polkadot_network,
+            require_payment_remark: env::var("REQUIRE_PAYMENT_REMARK")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse()
+                .context("REQUIRE_PAYMENT_REMARK must be true or false")?,
+            payment_nonce_ttl_secs: env::var("PAYMENT_NONCE_TTL_SECS")
+                .unwrap_or_else(|_| "600".to_string())
+                .parse()
+                .context("PAYMENT_NONCE_TTL_SECS must be a valid u64")?,
+            granted_payment_ttl_secs: env::var("GRANTED_PAYMENT_TTL_SECS")
+                .unwrap_or_else(|_| "86400".to_string())
+                .parse()
+                .context("GRANTED_PAYMENT_TTL_SECS must be a valid u64")?,
+            paid_route: PaidRoute {
+                confirmation: env::var("PAID_CONFIRMATION_LEVEL")
+                    .unwrap_or_else(|_| "finalized".to_string())
+                    .parse()
+                    .context("PAID_CONFIRMATION_LEVEL must be pool, best_block or finalized")?,
+                allow_payment_reuse: env::var("PAID_ALLOW_PAYMENT_REUSE")
+                    .unwrap_or_else(|_| "false".to_string())
+                    .parse()
+                    .context("PAID_ALLOW_PAYMENT_REUSE must be true or false")?,
+            },
         })
     }
 
@@ -39,3 +113,33 @@ impl Config {
         format!("{}:{}", self.server_host, self.server_port)
     }
 }
+
+/// Symbol of a network's native token, the default payment currency.
+fn native_currency(network: &str) -> &'static str {
+    match network.to_ascii_lowercase().as_str() {
+        "paseo" | "asset-hub-paseo" => "PAS",
+        "westend" | "asset-hub-westend" => "WND",
+        _ => "DOT",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_native_currency() {
+        assert_eq!(native_currency("paseo"), "PAS");
+        assert_eq!(native_currency("asset-hub-paseo"), "PAS");
+        assert_eq!(native_currency("Westend"), "WND");
+        assert_eq!(native_currency("polkadot"), "DOT");
+    }
+
+    #[test]
+    fn test_confirmation_level_parse() {
+        assert_eq!("pool".parse::<ConfirmationLevel>().unwrap(), ConfirmationLevel::Pool);
+        assert_eq!("best_block".parse::<ConfirmationLevel>().unwrap(), ConfirmationLevel::BestBlock);
+        assert_eq!("finalized".parse::<ConfirmationLevel>().unwrap(), ConfirmationLevel::Finalized);
+        assert!("best-block".parse::<ConfirmationLevel>().is_err());
+    }
+}
@@ -17,6 +17,12 @@ pub enum ServerError {
     #[error("Payment settlement failed: {0}")]
     PaymentSettlementFailed(String),
 
+    #[error("Payment {0} was already used")]
+    PaymentAlreadyUsed(String),
+
+    #[error("Payment pending: {0}")]
+    PaymentPending(String),
+
     #[error("Facilitator error: {0}")]
     FacilitatorError(String),
 
@@ -42,6 +48,8 @@ impl IntoResponse for ServerError {
             ServerError::PaymentRequired(_) => (StatusCode::PAYMENT_REQUIRED, "PaymentRequired"),
             ServerError::PaymentVerificationFailed(_) => (StatusCode::UNPROCESSABLE_ENTITY, "PaymentVerificationFailed"),
             ServerError::PaymentSettlementFailed(_) => (StatusCode::BAD_GATEWAY, "PaymentSettlementFailed"),
+            ServerError::PaymentAlreadyUsed(_) => (StatusCode::CONFLICT, "PaymentAlreadyUsed"),
+            ServerError::PaymentPending(_) => (StatusCode::ACCEPTED, "PaymentPending"),
             ServerError::FacilitatorError(_) => (StatusCode::BAD_GATEWAY, "FacilitatorError"),
             ServerError::InvalidPaymentHeader(_) => (StatusCode::BAD_REQUEST, "InvalidPaymentHeader"),
             ServerError::ConfigError(_) => (StatusCode::INTERNAL_SERVER_ERROR, "ConfigError"),
@@ -1,7 +1,19 @@
 use crate::error::{ServerError, ServerResult};
-use crate::facilitator::types::{SettleRequest, SettleResponse, VerifyRequest, VerifyResponse};
-use reqwest::Client;
-use tracing::{debug, error, info};
+use crate::facilitator::types::{
+    ConfirmationLevel, ExpectedPayment, SettleOutcome, SettleRequest, SettleResponse, SettledPayment,
+};
+use reqwest::{Client, StatusCode};
+use std::time::Duration;
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+/// Header the facilitator uses to recognise a retried `/settle` request.
+const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Attempts at a `/settle` request before giving up.
+const SETTLE_ATTEMPTS: u32 = 3;
+
+const SETTLE_RETRY_DELAY: Duration = Duration::from_secs(2);
 
 pub struct FacilitatorClient {
     base_url: String,
@@ -9,84 +21,73 @@ pub struct FacilitatorClient {
 }
 
 impl FacilitatorClient {
-    pub fn new(base_url: String) -> Self {
-        Self {
-            base_url,
-            client: Client::new(),
-        }
+    /// `timeout` bounds each request and should exceed the facilitator's
+    /// settlement timeout, so a timed out request is one worth retrying.
+    pub fn new(base_url: String, timeout: Duration) -> ServerResult<Self> {
+        let client = Client::builder()
+            .timeout(timeout)
+            .build()
+            .map_err(|e| ServerError::ConfigError(format!("Failed to build HTTP client: {}", e)))?;
+        Ok(Self { base_url, client })
     }
 
-    pub async fn verify_payment(
+    /// Verify a payment against its requirements and settle it, in a single
+    /// `/settle` request.
+    pub async fn settle_payment(
         &self,
         transaction: &str,
-        expected_amount: u128,
-        expected_recipient: &str,
-    ) -> ServerResult<bool> {
-        info!("Verifying payment with facilitator");
-
-        let url = format!("{}/verify", self.base_url);
-        let request = VerifyRequest {
-            transaction: transaction.to_string(),
-            expected_amount,
-            expected_recipient: expected_recipient.to_string(),
-        };
-
-        debug!("Sending verify request to: {}", url);
-
-        let response = self
-            .client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await
-            .map_err(|e| {
-                error!("Failed to call facilitator verify endpoint: {}", e);
-                ServerError::FacilitatorError(format!("Failed to verify payment: {}", e))
-            })?;
-
-        if !response.status().is_success() {
-            error!("Facilitator returned error status: {}", response.status());
-            return Err(ServerError::PaymentVerificationFailed(
-                "Facilitator verification failed".to_string(),
-            ));
-        }
-
-        let verify_response: VerifyResponse = response.json().await.map_err(|e| {
-            error!("Failed to parse verify response: {}", e);
-            ServerError::FacilitatorError(format!("Invalid response from facilitator: {}", e))
-        })?;
-
-        if verify_response.valid {
-            info!("Payment verified successfully");
-            Ok(true)
-        } else {
-            info!("Payment verification failed: {}", verify_response.message);
-            Err(ServerError::PaymentVerificationFailed(
-                verify_response.message,
-            ))
-        }
-    }
-
-    pub async fn settle_payment(&self, transaction: &str) -> ServerResult<String> {
-        info!("Settling payment with facilitator");
+        requirements: ExpectedPayment,
+        confirmation: ConfirmationLevel,
+    ) -> ServerResult<SettledPayment> {
+        info!("Verifying and settling payment with facilitator ({:?} confirmation)", confirmation);
 
         let url = format!("{}/settle", self.base_url);
         let request = SettleRequest {
             transaction: transaction.to_string(),
+            requirements,
+            confirmation,
         };
 
-        debug!("Sending settle request to: {}", url);
-
-        let response = self
-            .client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await
-            .map_err(|e| {
-                error!("Failed to call facilitator settle endpoint: {}", e);
-                ServerError::FacilitatorError(format!("Failed to settle payment: {}", e))
-            })?;
+        // Every attempt carries the same key, so a retry after a lost
+        // response gets the original result instead of a second submission.
+        let idempotency_key = Uuid::new_v4().to_string();
+        let mut attempt = 1;
+
+        let response = loop {
+            debug!("Sending settle request to: {} (attempt {})", url, attempt);
+
+            let result = self
+                .client
+                .post(&url)
+                .header(IDEMPOTENCY_KEY_HEADER, &idempotency_key)
+                .json(&request)
+                .send()
+                .await;
+
+            match result {
+                // The first attempt may still be running on the facilitator.
+                Ok(response) if response.status() == StatusCode::CONFLICT && attempt < SETTLE_ATTEMPTS => {
+                    warn!("Settlement still in progress, retrying");
+                }
+                Ok(response) if response.status() == StatusCode::CONFLICT => {
+                    warn!("Settlement still in progress after {} attempts", attempt);
+                    return Err(ServerError::PaymentPending(
+                        "Settlement is still in progress; retry with the same payment".to_string(),
+                    ));
+                }
+                Ok(response) => break response,
+                Err(e) if (e.is_timeout() || e.is_connect()) && attempt < SETTLE_ATTEMPTS => {
+                    warn!("Failed to reach facilitator settle endpoint, retrying: {}", e);
+                }
+                Err(e) => {
+                    error!("Failed to call facilitator settle endpoint: {}", e);
+                    return Err(ServerError::FacilitatorError(format!("Failed to settle payment: {}", e)));
+                }
+            }
+
+            attempt += 1;
+            tokio::time::sleep(SETTLE_RETRY_DELAY).await;
+        };
 
         let settle_response: SettleResponse = response.json().await.map_err(|e| {
             error!("Failed to parse settle response: {}", e);
@@ -94,11 +95,28 @@ impl FacilitatorClient {
         })?;
 
         if settle_response.settled {
-            let tx_hash = settle_response
-                .transaction_hash
-                .unwrap_or_else(|| "unknown".to_string());
-            info!("Payment settled successfully: {}", tx_hash);
-            Ok(tx_hash)
+            let settlement = settle_response.settlement.ok_or_else(|| {
+                error!("Facilitator settled payment without settlement details");
+                ServerError::FacilitatorError("Settle response is missing settlement details".to_string())
+            })?;
+            info!("Payment settled successfully: {}", settlement.tx_hash);
+            Ok(SettledPayment {
+                settlement,
+                replayed: settle_response.replayed,
+            })
+        } else if settle_response.outcome == SettleOutcome::Pending {
+            info!(
+                "Payment settlement pending: {}",
+                settle_response.settlement_id.as_deref().unwrap_or("unknown settlement")
+            );
+            Err(ServerError::PaymentPending(
+                "Settlement is not yet confirmed; retry with the same payment".to_string(),
+            ))
+        } else if settle_response.outcome == SettleOutcome::VerificationFailed {
+            info!("Payment verification failed: {}", settle_response.message);
+            Err(ServerError::PaymentVerificationFailed(
+                settle_response.message,
+            ))
         } else {
             error!("Payment settlement failed: {}", settle_response.message);
             Err(ServerError::PaymentSettlementFailed(
@@ -114,7 +132,7 @@ mod tests {
 
     #[test]
     fn test_facilitator_client_creation() {
-        let client = FacilitatorClient::new("http://localhost:8080".to_string());
+        let client = FacilitatorClient::new("http://localhost:8080".to_string(), Duration::from_secs(90)).unwrap();
         assert_eq!(client.base_url, "http://localhost:8080");
     }
 }
@@ -1,26 +1,113 @@
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use thiserror::Error;
 
+use crate::x402::PaymentAsset;
+
+/// What the facilitator checks the payment against before settling it.
 #[derive(Debug, Serialize)]
-pub struct VerifyRequest {
-    pub transaction: String,
+pub struct ExpectedPayment {
     pub expected_amount: u128,
     pub expected_recipient: String,
+    pub expected_asset: PaymentAsset,
+    pub network: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment_nonce: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
-pub struct VerifyResponse {
-    pub valid: bool,
-    pub message: String,
-}
-
+/// Verifies and settles a payment in one request.
 #[derive(Debug, Serialize)]
 pub struct SettleRequest {
     pub transaction: String,
+    pub requirements: ExpectedPayment,
+    pub confirmation: ConfirmationLevel,
+}
+
+/// How far the facilitator waits before reporting a settlement.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfirmationLevel {
+    /// Accepted into the transaction pool.
+    Pool,
+    /// Included in a best block that may still be retracted.
+    BestBlock,
+    /// Included in a finalized block.
+    #[default]
+    Finalized,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("unknown confirmation level {0:?}")]
+pub struct UnknownConfirmationLevel(String);
+
+impl FromStr for ConfirmationLevel {
+    type Err = UnknownConfirmationLevel;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pool" => Ok(ConfirmationLevel::Pool),
+            "best_block" => Ok(ConfirmationLevel::BestBlock),
+            "finalized" => Ok(ConfirmationLevel::Finalized),
+            other => Err(UnknownConfirmationLevel(other.to_string())),
+        }
+    }
+}
+
+/// How a `/settle` request ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SettleOutcome {
+    Settled,
+    Replayed,
+    Pending,
+    VerificationFailed,
+    SettlementFailed,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct SettleResponse {
+    pub outcome: SettleOutcome,
     pub settled: bool,
-    pub transaction_hash: Option<String>,
+    /// The facilitator returned the result of an earlier settlement.
+    #[serde(default)]
+    pub replayed: bool,
     pub message: String,
+    #[serde(default)]
+    pub settlement: Option<SettlementResult>,
+    /// Set for settlements the facilitator keeps following after answering.
+    #[serde(default)]
+    pub settlement_id: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct SettledPayment {
+    pub settlement: SettlementResult,
+    /// Settled by an earlier request rather than this one.
+    pub replayed: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SettlementStatus {
+    Validated,
+    InBestBlock,
+    Finalized,
+    Failed,
+    Reverted,
+    Pending,
+    Unknown,
+}
+
+/// Where a settled payment landed on chain, as reported by the facilitator.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettlementResult {
+    pub tx_hash: String,
+    /// Block position, absent for settlements confirmed at `pool` level.
+    pub block_hash: Option<String>,
+    pub block_number: Option<u64>,
+    pub extrinsic_index: Option<u32>,
+    pub extrinsic_id: Option<String>,
+    pub network: String,
+    pub explorer_url: Option<String>,
+    pub status: SettlementStatus,
 }
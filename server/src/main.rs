@@ -10,6 +10,7 @@ use axum::{
     Router,
 };
 use std::sync::Arc;
+use std::time::Duration;
 use tower_http::cors::{Any, CorsLayer};
 use tower_http::trace::TraceLayer;
 use tracing::{info, Level};
@@ -19,6 +20,7 @@ use crate::{
     api::{routes::AppStateInner, AppState},
     config::Config,
     facilitator::FacilitatorClient,
+    x402::{GrantedPayments, PaymentNonces},
 };
 
 #[tokio::main]
@@ -30,11 +32,16 @@ async fn main() -> Result<()> {
     let config = Config::from_env()?;
     info!("Configuration loaded: network={}", config.polkadot_network);
 
-    let facilitator_client = FacilitatorClient::new(config.facilitator_url.clone());
+    let facilitator_client = FacilitatorClient::new(
+        config.facilitator_url.clone(),
+        Duration::from_secs(config.facilitator_timeout_secs),
+    )?;
 
     let state: AppState = Arc::new(AppStateInner {
         config: config.clone(),
         facilitator_client,
+        payment_nonces: PaymentNonces::new(Duration::from_secs(config.payment_nonce_ttl_secs)),
+        granted_payments: GrantedPayments::new(Duration::from_secs(config.granted_payment_ttl_secs)),
     });
 
     let app = create_router(state);
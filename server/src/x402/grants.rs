@@ -0,0 +1,57 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Payments this server has already granted access for, by extrinsic hash.
+/// The facilitator reports any earlier settlement of a payment as replayed,
+/// including one whose first request never got access; only payments
+/// recorded here count as used. Grants are kept for `ttl`, which should
+/// outlast a payment's mortality period.
+pub struct GrantedPayments {
+    ttl: Duration,
+    granted: Mutex<HashMap<String, Instant>>,
+}
+
+impl GrantedPayments {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            granted: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Record that a payment granted access, returning false if it already
+    /// had. Drops any grants that have expired.
+    pub fn grant(&self, tx_hash: &str) -> bool {
+        let now = Instant::now();
+
+        let mut granted = self.granted.lock().unwrap();
+        granted.retain(|_, granted_at| now.duration_since(*granted_at) < self.ttl);
+        if granted.contains_key(tx_hash) {
+            return false;
+        }
+        granted.insert(tx_hash.to_string(), now);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grant_once() {
+        let granted = GrantedPayments::new(Duration::from_secs(60));
+        assert!(granted.grant("0x01"));
+        assert!(!granted.grant("0x01"));
+        assert!(granted.grant("0x02"));
+    }
+
+    #[test]
+    fn test_grant_expiry() {
+        let granted = GrantedPayments::new(Duration::ZERO);
+        assert!(granted.grant("0x01"));
+        assert!(granted.grant("0x01"));
+        assert_eq!(granted.granted.lock().unwrap().len(), 1);
+    }
+}
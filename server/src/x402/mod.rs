@@ -1,5 +1,10 @@
+pub mod grants;
+pub mod nonce;
 pub mod protocol;
+pub mod ss58;
 pub mod types;
 
+pub use grants::GrantedPayments;
+pub use nonce::PaymentNonces;
 pub use protocol::*;
 pub use types::*;
@@ -0,0 +1,95 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use uuid::Uuid;
+
+/// Payment nonces issued with 402 challenges and not yet redeemed. A client
+/// binds its payment to a challenge by batching the nonce as a remark.
+pub struct PaymentNonces {
+    ttl: Duration,
+    issued: Mutex<HashMap<String, Instant>>,
+}
+
+impl PaymentNonces {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            issued: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Issue a fresh nonce, dropping any that have expired.
+    pub fn issue(&self) -> String {
+        let nonce = Uuid::new_v4().simple().to_string();
+        let now = Instant::now();
+
+        let mut issued = self.issued.lock().unwrap();
+        issued.retain(|_, issued_at| now.duration_since(*issued_at) < self.ttl);
+        issued.insert(nonce.clone(), now);
+        nonce
+    }
+
+    /// Redeem a pending nonce so it cannot unlock another request. Returns
+    /// when it was issued, for `restore` if the payment then fails, or `None`
+    /// if the nonce is unknown, expired or already redeemed.
+    pub fn consume(&self, nonce: &str) -> Option<Instant> {
+        match self.issued.lock().unwrap().remove(nonce) {
+            Some(issued_at) if issued_at.elapsed() < self.ttl => Some(issued_at),
+            _ => None,
+        }
+    }
+
+    /// Return a redeemed nonce whose payment did not go through, keeping its
+    /// original expiry.
+    pub fn restore(&self, nonce: &str, issued_at: Instant) {
+        self.issued.lock().unwrap().insert(nonce.to_string(), issued_at);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn test_nonce_lifecycle() {
+        let nonces = PaymentNonces::new(Duration::from_secs(60));
+        let nonce = nonces.issue();
+
+        assert!(nonces.consume("unknown").is_none());
+        let issued_at = nonces.consume(&nonce).unwrap();
+        assert!(nonces.consume(&nonce).is_none());
+
+        nonces.restore(&nonce, issued_at);
+        assert_eq!(nonces.consume(&nonce), Some(issued_at));
+    }
+
+    #[test]
+    fn test_nonce_expiry() {
+        let nonces = PaymentNonces::new(Duration::ZERO);
+        let nonce = nonces.issue();
+        assert!(nonces.consume(&nonce).is_none());
+    }
+
+    #[test]
+    fn test_concurrent_consume() {
+        let nonces = Arc::new(PaymentNonces::new(Duration::from_secs(60)));
+        let nonce = nonces.issue();
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let nonces = nonces.clone();
+                let nonce = nonce.clone();
+                thread::spawn(move || nonces.consume(&nonce).is_some())
+            })
+            .collect();
+        let redeemed = handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .filter(|redeemed| *redeemed)
+            .count();
+        assert_eq!(redeemed, 1);
+    }
+}
@@ -9,12 +9,20 @@ use crate::x402::types::{PaymentHeader, PaymentRequirements};
 use crate::error::ServerError;
 
 pub const PAYMENT_HEADER_NAME: &str = "x-payment";
+pub const PAYMENT_NONCE_HEADER_NAME: &str = "x-payment-nonce";
 
 pub fn extract_payment_header(headers: &HeaderMap) -> Option<PaymentHeader> {
-    headers
+    let mut payment_header = headers
         .get(PAYMENT_HEADER_NAME)
         .and_then(|v| v.to_str().ok())
-        .and_then(PaymentHeader::from_header)
+        .and_then(PaymentHeader::from_header)?;
+
+    payment_header.nonce = headers
+        .get(PAYMENT_NONCE_HEADER_NAME)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+
+    Some(payment_header)
 }
 
 pub fn create_payment_required_response(requirements: PaymentRequirements) -> Response {
@@ -0,0 +1,92 @@
+use blake2::{Blake2b512, Digest};
+use thiserror::Error;
+
+const SS58_CHECKSUM_PREFIX: &[u8] = b"SS58PRE";
+const CHECKSUM_LEN: usize = 2;
+const ACCOUNT_ID_LEN: usize = 32;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Ss58Error {
+    #[error("not valid base58")]
+    InvalidBase58,
+
+    #[error("unsupported address prefix")]
+    InvalidPrefix,
+
+    #[error("expected a 32-byte account, got {0} bytes")]
+    InvalidLength(usize),
+
+    #[error("checksum mismatch")]
+    InvalidChecksum,
+}
+
+/// Decode an SS58 address into its network prefix and AccountId32 bytes,
+/// verifying the checksum.
+pub fn decode_ss58(address: &str) -> Result<(u16, [u8; ACCOUNT_ID_LEN]), Ss58Error> {
+    let data = bs58::decode(address)
+        .into_vec()
+        .map_err(|_| Ss58Error::InvalidBase58)?;
+
+    let (prefix, prefix_len) = match data.first() {
+        Some(0..=63) => (data[0] as u16, 1),
+        Some(64..=127) if data.len() > 1 => {
+            let lower = ((data[0] & 0b0011_1111) << 2) | (data[1] >> 6);
+            let upper = data[1] & 0b0011_1111;
+            (lower as u16 | ((upper as u16) << 8), 2)
+        }
+        _ => return Err(Ss58Error::InvalidPrefix),
+    };
+
+    let body_len = data.len().saturating_sub(prefix_len + CHECKSUM_LEN);
+    if body_len != ACCOUNT_ID_LEN {
+        return Err(Ss58Error::InvalidLength(body_len));
+    }
+
+    let (payload, checksum) = data.split_at(prefix_len + ACCOUNT_ID_LEN);
+    let hash = Blake2b512::new()
+        .chain_update(SS58_CHECKSUM_PREFIX)
+        .chain_update(payload)
+        .finalize();
+    if hash[..CHECKSUM_LEN] != *checksum {
+        return Err(Ss58Error::InvalidChecksum);
+    }
+
+    let mut account = [0u8; ACCOUNT_ID_LEN];
+    account.copy_from_slice(&payload[prefix_len..]);
+    Ok((prefix, account))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALICE: &str = "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d";
+
+    #[test]
+    fn test_decode_generic_address() {
+        let (prefix, account) = decode_ss58("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY").unwrap();
+        assert_eq!(prefix, 42);
+        assert_eq!(hex::encode(account), ALICE);
+    }
+
+    #[test]
+    fn test_decode_polkadot_and_kusama_addresses() {
+        let (prefix, account) = decode_ss58("15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5").unwrap();
+        assert_eq!(prefix, 0);
+        assert_eq!(hex::encode(account), ALICE);
+
+        let (prefix, account) = decode_ss58("HNZata7iMYWmk5RvZRTiAsSDhV8366zq2YGb3tLH5Upf74F").unwrap();
+        assert_eq!(prefix, 2);
+        assert_eq!(hex::encode(account), ALICE);
+    }
+
+    #[test]
+    fn test_decode_invalid_addresses() {
+        assert_eq!(decode_ss58("your_receiver_address_here"), Err(Ss58Error::InvalidBase58));
+        assert_eq!(
+            decode_ss58("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQZ"),
+            Err(Ss58Error::InvalidChecksum)
+        );
+        assert!(matches!(decode_ss58("11111"), Err(Ss58Error::InvalidLength(_))));
+    }
+}
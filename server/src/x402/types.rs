@@ -1,23 +1,54 @@
 use serde::{Deserialize, Serialize};
 
+/// What a payment must be made in.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PaymentAsset {
+    /// The network's native token.
+    #[default]
+    Native,
+    /// A `pallet-assets` asset on Asset Hub, e.g. USDT (1984) or USDC (1337).
+    Asset { id: u32 },
+    /// A `ForeignAssets` asset on Asset Hub, identified by its XCM location
+    /// in scale-value syntax, e.g. `{ parents: 1, interior: X1((Parachain(2011))) }`.
+    ForeignAsset { location: String },
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PaymentRequirements {
     pub amount: u128,
     pub recipient: String,
     pub network: String,
+    pub asset: PaymentAsset,
     pub currency: String,
+    /// Nonce to batch with the transfer as a `System::remark_with_event`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
 }
 
 impl PaymentRequirements {
-    pub fn new(amount: u128, recipient: String, network: String) -> Self {
+    pub fn new(
+        amount: u128,
+        recipient: String,
+        network: String,
+        asset: PaymentAsset,
+        currency: String,
+    ) -> Self {
         Self {
             amount,
             recipient,
             network,
-            currency: "DOT".to_string(),
+            asset,
+            currency,
+            nonce: None,
         }
     }
 
+    pub fn with_nonce(mut self, nonce: String) -> Self {
+        self.nonce = Some(nonce);
+        self
+    }
+
     pub fn to_header_value(&self) -> String {
         serde_json::to_string(self).unwrap_or_default()
     }
@@ -26,12 +57,14 @@ impl PaymentRequirements {
 #[derive(Debug, Clone)]
 pub struct PaymentHeader {
     pub transaction: String,
+    pub nonce: Option<String>,
 }
 
 impl PaymentHeader {
     pub fn from_header(header_value: &str) -> Option<Self> {
         Some(Self {
             transaction: header_value.to_string(),
+            nonce: None,
         })
     }
 }